@@ -12,6 +12,13 @@ enum TabName {
     Logs,
     Settings,
     ExitSelect,
+    Account,
+}
+
+#[derive(Clone, PartialEq)]
+enum LogView {
+    Traffic,
+    Events,
 }
 
 fn main() {
@@ -27,11 +34,34 @@ fn main() {
 
 struct Demo {
     tab: TabName,
+    log_view: LogView,
     switch_on: bool,
+    logged_in: bool,
+    tracker_lists: [bool; 3],
     settings_on: bool,
-    selected: String,
+    settings_toggle: PendingToggle,
+    settings_started: f64,
+    settings_attempts: u32,
+    selected: Option<String>,
+    protocols: Vec<String>,
+    exit_regions: OptionSource,
+    selected_region: Option<String>,
     exit_selector_show_all: bool,
     selected_exit: Option<ExitDescriptor>,
+    socks_proxy: String,
+    timeout_secs: u32,
+    bandwidth_limit: f32,
+    preferences: Preferences,
+    last_saved: Option<String>,
+}
+
+#[derive(Default)]
+struct Preferences {
+    autoconnect: bool,
+    kill_switch: bool,
+    theme: Option<String>,
+    device_name: String,
+    socks_port: u16,
 }
 
 impl Demo {
@@ -46,13 +76,34 @@ impl Demo {
         });
         ctx.set_zoom_factor(1.1);
 
+        let exit_regions = OptionSource::new();
+        load_exit_regions(ctx, &exit_regions);
+
         Self {
             tab: TabName::Dashboard,
+            log_view: LogView::Traffic,
             switch_on: false,
+            logged_in: false,
+            tracker_lists: [true, false, true],
             settings_on: false,
-            selected: String::new(),
+            settings_toggle: PendingToggle::default(),
+            settings_started: 0.0,
+            settings_attempts: 0,
+            selected: None,
+            protocols: vec!["sosistab".to_string()],
+            exit_regions,
+            selected_region: None,
             exit_selector_show_all: false,
             selected_exit: None,
+            socks_proxy: "127.0.0.1:9909".to_string(),
+            timeout_secs: 30,
+            bandwidth_limit: 50.0,
+            preferences: Preferences {
+                device_name: "My laptop".to_string(),
+                socks_port: 9909,
+                ..Default::default()
+            },
+            last_saved: None,
         }
     }
 }
@@ -64,36 +115,49 @@ impl eframe::App for Demo {
             fill: ctx.style().visuals.panel_fill,
             ..Default::default()
         };
-        CentralPanel::default().frame(frame).show(ctx, |ui| {
-            TabBar::new(
-                "tab_bar",
-                vec![
-                    TabBarItem::new(
-                        TabName::Dashboard,
-                        String::from("Dashboard"),
-                        ICON_DASHBOARD.to_string(),
-                    ),
-                    TabBarItem::new(
-                        TabName::Logs,
-                        String::from("Logs"),
-                        ICON_DESCRIPTION.to_string(),
-                    ),
-                    TabBarItem::new(
-                        TabName::Settings,
-                        String::from("Settings"),
-                        ICON_SETTINGS.to_string(),
-                    ),
-                    TabBarItem::new(
-                        TabName::ExitSelect,
-                        String::from("Exit Select"),
-                        ICON_LOCATION_PIN.to_string(),
-                    ),
-                ],
-                64.0,
-            )
-            .show(ctx, &mut self.tab);
-
-            match self.tab {
+        TabBar::new(
+            "tab_bar",
+            vec![
+                TabBarItem::new(
+                    TabName::Dashboard,
+                    String::from("Dashboard"),
+                    ICON_DASHBOARD.to_string(),
+                ),
+                TabBarItem::new(
+                    TabName::Logs,
+                    String::from("Logs"),
+                    ICON_DESCRIPTION.to_string(),
+                ),
+                TabBarItem::new(
+                    TabName::Settings,
+                    String::from("Settings"),
+                    ICON_SETTINGS.to_string(),
+                ),
+                TabBarItem::new(
+                    TabName::ExitSelect,
+                    String::from("Exit Select"),
+                    ICON_LOCATION_PIN.to_string(),
+                )
+                .disabled_reason(String::from("Sign in to choose an exit"))
+                .disable(!self.logged_in),
+                TabBarItem::new(
+                    TabName::Account,
+                    String::from("Account"),
+                    ICON_ACCOUNT_CIRCLE.to_string(),
+                )
+                .hidden(!self.logged_in),
+            ],
+            64.0,
+        )
+        .placement(TabBarPlacement::Auto)
+        .animation_duration(0.25)
+        .keyboard_shortcuts(true)
+        .overflow(TabBarOverflow::More)
+        .show(ctx, &mut self.tab);
+
+        CentralPanel::default()
+            .frame(frame)
+            .show(ctx, |ui| match self.tab {
                 TabName::Dashboard => {
                     ui.add(Button::primary(
                         "Log in with existing account".to_string(),
@@ -122,92 +186,369 @@ impl eframe::App for Demo {
                         Button::green("Hello world".to_string(), ButtonSize::Small).invert(true),
                     );
 
-                    ui.add(Switch::new(&mut self.switch_on));
+                    ui.add(
+                        Switch::new(&mut self.switch_on).label("Connect on startup".to_string()),
+                    );
+                    ui.add(
+                        Switch::new(&mut self.switch_on)
+                            .label("Themed".to_string())
+                            .label_position(LabelPosition::Leading)
+                            .style(SwitchStyle::from_visuals(ui.visuals())),
+                    );
+                    ui.add(
+                        Switch::new(&mut self.switch_on)
+                            .label("Disabled".to_string())
+                            .disable(true),
+                    );
+                    ui.add(Switch::new(&mut self.logged_in).label("Signed in".to_string()));
+
+                    let mut block_all = if self.tracker_lists.iter().all(|on| *on) {
+                        Some(true)
+                    } else if self.tracker_lists.iter().all(|on| !*on) {
+                        Some(false)
+                    } else {
+                        None
+                    };
+                    if ui
+                        .add(
+                            TriStateSwitch::new(&mut block_all)
+                                .label("Block all trackers".to_string()),
+                        )
+                        .changed()
+                    {
+                        self.tracker_lists = [block_all == Some(true); 3];
+                    }
+                    for (on, name) in
+                        self.tracker_lists
+                            .iter_mut()
+                            .zip(["Advertising", "Analytics", "Social"])
+                    {
+                        ui.add(Switch::new(on).label(name.to_string()));
+                    }
                 }
                 TabName::Logs => {
-                    ui.add(Card::new(|ui| {
-                        let text_size = 16.0;
-                        ui.spacing_mut().item_spacing = vec2(0.0, 8.0);
-
-                        ui.style_mut().override_text_style = Some(TextStyle::Body);
-                        ui.style_mut().spacing.icon_width = 24.0;
-                        ui.style_mut().spacing.icon_spacing = 8.0;
-
-                        ui.horizontal(|ui| {
-                            ui.label(RichText::new(ICON_DOWNLOAD).size(text_size));
-                            ui.label(RichText::new(" Download").size(text_size));
-                            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                                ui.label(
-                                    RichText::new("- MB").size(text_size).color(Color32::BLUE),
-                                );
-                            });
-                        });
+                    let tab_bar = TabBar::new(
+                        "log_tabs",
+                        vec![
+                            TabBarItem::new(
+                                LogView::Traffic,
+                                String::from("Traffic"),
+                                ICON_SPEED.to_string(),
+                            ),
+                            TabBarItem::new(
+                                LogView::Events,
+                                String::from("Events"),
+                                ICON_HISTORY.to_string(),
+                            ),
+                        ],
+                        40.0,
+                    )
+                    .appearance(TabBarAppearance::Underline)
+                    .placement(TabBarPlacement::Top)
+                    .style(TabBarStyle::from_visuals(ui.visuals()).label_size(14.0));
 
-                        ui.horizontal(|ui| {
-                            ui.label(RichText::new(ICON_UPLOAD).size(text_size));
-                            ui.label(RichText::new(" Upload").size(text_size));
-                            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                                ui.label(
-                                    RichText::new("- MB")
-                                        .size(text_size)
-                                        .color(Color32::DARK_RED),
-                                );
-                            });
-                        });
+                    let log_tabs = TabView::new("log_view", tab_bar)
+                        .page(LogView::Traffic, |ui| {
+                            ui.add(Card::new(|ui| {
+                                let text_size = 16.0;
+                                ui.spacing_mut().item_spacing = vec2(0.0, 8.0);
 
-                        ui.horizontal(|ui| {
-                            ui.label(RichText::new(ICON_SWAP_VERT).size(text_size));
-                            ui.label(RichText::new(" Latency").size(text_size));
-                            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                                ui.label(RichText::new("- ms").size(text_size));
-                            });
-                        });
+                                ui.style_mut().override_text_style = Some(TextStyle::Body);
+                                ui.style_mut().spacing.icon_width = 24.0;
+                                ui.style_mut().spacing.icon_spacing = 8.0;
 
-                        ui.horizontal(|ui| {
-                            ui.label(RichText::new(ICON_COMPUTER).size(text_size));
-                            ui.label(RichText::new(" Via").size(text_size));
-                            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                                ui.label(RichText::new("-").size(text_size));
-                            });
-                        });
+                                ui.horizontal(|ui| {
+                                    ui.label(RichText::new(ICON_DOWNLOAD).size(text_size));
+                                    ui.label(RichText::new(" Download").size(text_size));
+                                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                        ui.label(
+                                            RichText::new("- MB")
+                                                .size(text_size)
+                                                .color(Color32::BLUE),
+                                        );
+                                    });
+                                });
 
-                        ui.horizontal(|ui| {
-                            ui.label(RichText::new(ICON_RADIO).size(text_size));
-                            ui.label(RichText::new(" Protocol").size(text_size));
-                            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                                ui.label(RichText::new("-").size(text_size));
-                            });
-                        });
-                    }));
+                                ui.horizontal(|ui| {
+                                    ui.label(RichText::new(ICON_UPLOAD).size(text_size));
+                                    ui.label(RichText::new(" Upload").size(text_size));
+                                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                        ui.label(
+                                            RichText::new("- MB")
+                                                .size(text_size)
+                                                .color(Color32::DARK_RED),
+                                        );
+                                    });
+                                });
+
+                                ui.horizontal(|ui| {
+                                    ui.label(RichText::new(ICON_SWAP_VERT).size(text_size));
+                                    ui.label(RichText::new(" Latency").size(text_size));
+                                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                        ui.label(RichText::new("- ms").size(text_size));
+                                    });
+                                });
+
+                                ui.horizontal(|ui| {
+                                    ui.label(RichText::new(ICON_COMPUTER).size(text_size));
+                                    ui.label(RichText::new(" Via").size(text_size));
+                                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                        ui.label(RichText::new("-").size(text_size));
+                                    });
+                                });
+
+                                ui.horizontal(|ui| {
+                                    ui.label(RichText::new(ICON_RADIO).size(text_size));
+                                    ui.label(RichText::new(" Protocol").size(text_size));
+                                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                        ui.label(RichText::new("-").size(text_size));
+                                    });
+                                });
+                            }));
+                        })
+                        .page(LogView::Events, |ui| {
+                            ui.label(RichText::new("No events yet").weak());
+                        })
+                        .show_inside(ui, &mut self.log_view);
+                    log_tabs.items[1]
+                        .clone()
+                        .on_hover_text("Connection and error events");
                 }
                 TabName::Settings => {
-                    ui.add(Card::new(|ui| {
-                        ui.add(SettingsLine::new(
-                            ICON_LAN.to_string(),
-                            String::from("Listen on all interfaces"),
-                            Box::new(|ui: &mut Ui| ui.add(Switch::new(&mut self.settings_on))),
-                        ));
-
-                        let options = vec![
-                            "English".to_string(),
-                            "Traditional Chinese".to_string(),
-                            "Simplified Chinese".to_string(),
-                            "Persian".to_string(),
-                            "Svitannski".to_string(),
-                        ];
-
-                        ui.add(SettingsLine::new(
-                            ICON_LANGUAGE.to_string(),
-                            "Language".to_string(),
-                            Box::new(|ui: &mut Ui| {
-                                ui.add(Dropdown::new(
-                                    "my_dropdown",
-                                    options.clone(),
-                                    &mut self.selected,
+                    SettingsSection::new()
+                        .header("General".to_string())
+                        .footer("Network changes apply the next time you connect.".to_string())
+                        .show(ui, |rows| {
+                            let now = rows.ui().input(|i| i.time);
+                            if self.settings_toggle.is_pending()
+                                && now - self.settings_started > 1.5
+                            {
+                                self.settings_attempts += 1;
+                                if self.settings_attempts % 2 == 1 {
+                                    self.settings_toggle.rollback(&mut self.settings_on);
+                                } else {
+                                    self.settings_toggle.confirm();
+                                }
+                            }
+
+                            rows.add(SettingsLine::new(
+                                ICON_LAN.to_string(),
+                                String::from("Listen on all interfaces"),
+                                Box::new(|ui: &mut Ui| {
+                                    let response = ui.add(
+                                        Switch::new(&mut self.settings_on)
+                                            .pending(&mut self.settings_toggle),
+                                    );
+                                    if response.changed() {
+                                        self.settings_started = now;
+                                    }
+                                    response
+                                }),
+                            ));
+
+                            let options = vec![
+                                DropdownItem::Header("Recent".to_string()),
+                                "English".into(),
+                                DropdownItem::Separator,
+                                DropdownItem::Header("All".to_string()),
+                                "English".into(),
+                                DropdownOption::new("Traditional Chinese".to_string())
+                                    .description("繁體中文".to_string())
+                                    .into(),
+                                DropdownOption::new("Simplified Chinese".to_string())
+                                    .description("简体中文".to_string())
+                                    .into(),
+                                DropdownOption::new("Persian".to_string())
+                                    .description("فارسی".to_string())
+                                    .disabled_reason("Install a Persian font to enable".to_string())
+                                    .into(),
+                                "Svitannski".into(),
+                            ];
+
+                            rows.add(
+                                SettingsLine::value(
+                                    ICON_INFO.to_string(),
+                                    "Version".to_string(),
+                                    "0.1.0".to_string(),
+                                )
+                                .subtitle("Up to date".to_string()),
+                            );
+
+                            if rows
+                                .add(SettingsLine::navigation(
+                                    ICON_DNS.to_string(),
+                                    "DNS".to_string(),
+                                    Some("Automatic".to_string()),
                                 ))
-                            }),
-                        ));
-                    }));
+                                .clicked()
+                            {
+                                self.tab = TabName::Logs;
+                            }
+
+                            rows.add(
+                                SettingsLine::navigation(
+                                    ICON_VPN_KEY.to_string(),
+                                    "Split tunneling".to_string(),
+                                    None,
+                                )
+                                .subtitle("Available on Plus".to_string())
+                                .disable(true),
+                            );
+
+                            rows.add(SettingsLine::new(
+                                ICON_LANGUAGE.to_string(),
+                                "Language".to_string(),
+                                Box::new(|ui: &mut Ui| {
+                                    ui.add(
+                                        Dropdown::new(
+                                            "my_dropdown",
+                                            options.clone(),
+                                            &mut self.selected,
+                                        )
+                                        .placeholder("Choose a language…".to_string())
+                                        .clearable(true)
+                                        .searchable(true)
+                                        .min_width(200.0),
+                                    )
+                                }),
+                            ));
+
+                            let protocols = vec![
+                                "sosistab".to_string(),
+                                "sosistab2".to_string(),
+                                "sosistab3".to_string(),
+                                "tls".to_string(),
+                            ];
+
+                            rows.add(SettingsLine::new(
+                                ICON_RADIO.to_string(),
+                                "Protocols".to_string(),
+                                Box::new(|ui: &mut Ui| {
+                                    ui.add(MultiDropdown::new(
+                                        "protocols",
+                                        protocols.clone(),
+                                        &mut self.protocols,
+                                    ))
+                                }),
+                            ));
+
+                            if self.exit_regions.take_retry_request() {
+                                load_exit_regions(rows.ui().ctx(), &self.exit_regions);
+                            }
+
+                            rows.add(SettingsLine::new(
+                                ICON_LOCATION_PIN.to_string(),
+                                "Exit region".to_string(),
+                                Box::new(|ui: &mut Ui| {
+                                    ui.add(
+                                        Dropdown::from_source(
+                                            "exit_region",
+                                            &self.exit_regions,
+                                            &mut self.selected_region,
+                                        )
+                                        .placeholder("Automatic".to_string()),
+                                    )
+                                }),
+                            ));
+
+                            rows.add(SettingsLine::new(
+                                ICON_LAN.to_string(),
+                                "SOCKS proxy".to_string(),
+                                Box::new(|ui: &mut Ui| {
+                                    ui.add(
+                                        TextField::new(&mut self.socks_proxy)
+                                            .hint("host:port".to_string())
+                                            .validate(|text| {
+                                                text.parse::<std::net::SocketAddr>()
+                                                    .map(|_| ())
+                                                    .map_err(|_| {
+                                                        "Expected an address like 127.0.0.1:9909"
+                                                            .to_string()
+                                                    })
+                                            }),
+                                    )
+                                }),
+                            ));
+
+                            rows.add(SettingsLine::new(
+                                ICON_TIMER.to_string(),
+                                "Connection timeout".to_string(),
+                                Box::new(|ui: &mut Ui| {
+                                    ui.add(Stepper::new(&mut self.timeout_secs, 5..=120).step(5.0))
+                                }),
+                            ));
+
+                            rows.add(SettingsLine::new(
+                                ICON_SPEED.to_string(),
+                                "Bandwidth limit".to_string(),
+                                Box::new(|ui: &mut Ui| {
+                                    ui.add(
+                                        ValueSlider::new(&mut self.bandwidth_limit, 1.0..=100.0)
+                                            .step(0.5)
+                                            .suffix(" Mbps".to_string()),
+                                    )
+                                }),
+                            ));
+                        });
+
+                    ui.add_space(16.0);
+
+                    let page = SettingsPage::new("preferences")
+                        .searchable(true)
+                        .section("Connection".to_string())
+                        .toggle(
+                            "autoconnect".to_string(),
+                            ICON_POWER.to_string(),
+                            "Connect on startup".to_string(),
+                            |p: &mut Preferences| &mut p.autoconnect,
+                        )
+                        .toggle(
+                            "kill_switch".to_string(),
+                            ICON_SHIELD.to_string(),
+                            "Kill switch".to_string(),
+                            |p| &mut p.kill_switch,
+                        )
+                        .keywords(vec!["firewall".to_string(), "leak".to_string()])
+                        .number(
+                            "socks_port".to_string(),
+                            ICON_LAN.to_string(),
+                            "SOCKS port".to_string(),
+                            1024..=65535,
+                            |p| &mut p.socks_port,
+                        )
+                        .keywords(vec!["proxy".to_string()])
+                        .section("Appearance".to_string())
+                        .choice(
+                            "theme".to_string(),
+                            ICON_PALETTE.to_string(),
+                            "Theme".to_string(),
+                            vec!["System", "Light", "Dark"],
+                            |p| &mut p.theme,
+                        )
+                        .text(
+                            "device_name".to_string(),
+                            ICON_DEVICES.to_string(),
+                            "Device name".to_string(),
+                            |p| &mut p.device_name,
+                        )
+                        .section("About".to_string())
+                        .link(
+                            "logs".to_string(),
+                            ICON_DESCRIPTION.to_string(),
+                            "View logs".to_string(),
+                            None,
+                        );
+
+                    let result = page.show(ui, &mut self.preferences);
+                    if let Some(key) = result.changed.last() {
+                        self.last_saved = Some(key.clone());
+                    }
+                    if let Some(key) = &self.last_saved {
+                        ui.label(RichText::new(format!("Saved {key}")).weak());
+                    }
+                    if result.clicked_link.as_deref() == Some("logs") {
+                        self.tab = TabName::Logs;
+                    }
                 }
                 TabName::ExitSelect => {
                     let exits = vec![
@@ -270,7 +611,23 @@ impl eframe::App for Demo {
                     );
                     exit_server_list.show(ui);
                 }
-            }
-        });
+                TabName::Account => {
+                    ui.label(RichText::new("Account").heading());
+                    if ui.button("Sign out").clicked() {
+                        self.logged_in = false;
+                    }
+                }
+            });
     }
 }
+
+fn load_exit_regions(ctx: &Context, source: &OptionSource) {
+    let ctx = ctx.clone();
+    let source = source.clone();
+
+    std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_secs(2));
+        source.set_loaded(vec!["Singapore", "United States", "Germany", "Japan"]);
+        ctx.request_repaint();
+    });
+}
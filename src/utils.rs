@@ -1,5 +1,9 @@
-use egui::{Color32, FontData, FontDefinitions, FontFamily};
+use std::ops::Range;
+
+use egui::{text::LayoutJob, Align, Color32, FontData, FontDefinitions, FontFamily, FontSelection};
+use egui::{RichText, Ui, WidgetText};
 use egui_material_icons::FONT_DATA;
+use unicode_normalization::char::{decompose_canonical, is_combining_mark};
 
 pub fn insert_icon_font(fonts: &mut FontDefinitions) {
     let material_icon_data = FontData::from_static(FONT_DATA);
@@ -22,3 +26,135 @@ fn increase_opacity(color: Color32, increase_percentage: f32) -> Color32 {
     let new_alpha = (color.a() as f32 * (1.0 + increase_percentage)).min(255.0) as u8;
     Color32::from_rgba_unmultiplied(color.r(), color.g(), color.b(), new_alpha)
 }
+
+pub fn match_ranges(text: &str, query: &str) -> Vec<Range<usize>> {
+    let query: Vec<char> = fold(query).into_iter().map(|(c, _)| c).collect();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let folded = fold(text);
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while start + query.len() <= folded.len() {
+        let window = &folded[start..start + query.len()];
+        if window.iter().map(|(c, _)| *c).eq(query.iter().copied()) {
+            ranges.push(window[0].1.start..window[query.len() - 1].1.end);
+            start += query.len();
+        } else {
+            start += 1;
+        }
+    }
+
+    ranges
+}
+
+pub fn highlight_matches(ui: &Ui, text: &str, query: &str) -> WidgetText {
+    let mut job = LayoutJob::default();
+    append_highlighted(&mut job, ui, text, query);
+    job.into()
+}
+
+pub(crate) fn append_highlighted(job: &mut LayoutJob, ui: &Ui, text: &str, query: &str) {
+    let highlight = ui.visuals().selection.bg_fill;
+    let mut append = |part: &str, highlighted: bool| {
+        let mut text = RichText::new(part);
+        if highlighted {
+            text = text.background_color(highlight);
+        }
+        text.append_to(job, ui.style(), FontSelection::Default, Align::Center);
+    };
+
+    let mut last = 0;
+    for range in match_ranges(text, query) {
+        append(&text[last..range.start], false);
+        append(&text[range.clone()], true);
+        last = range.end;
+    }
+    append(&text[last..], false);
+}
+
+fn fold(text: &str) -> Vec<(char, Range<usize>)> {
+    let mut folded: Vec<(char, Range<usize>)> = Vec::new();
+    for (index, c) in text.char_indices() {
+        let range = index..index + c.len_utf8();
+        let mut pushed = false;
+        for c in c.to_lowercase() {
+            decompose_canonical(c, |c| {
+                if is_combining_mark(c) {
+                    return;
+                }
+                for c in fold_letter(c).chars() {
+                    folded.push((c, range.clone()));
+                    pushed = true;
+                }
+            });
+        }
+        if !pushed {
+            if let Some((_, last)) = folded.last_mut() {
+                last.end = range.end;
+            }
+        }
+    }
+    folded
+}
+
+fn fold_letter(c: char) -> String {
+    match c {
+        'æ' => "ae".to_string(),
+        'œ' => "oe".to_string(),
+        'ß' => "ss".to_string(),
+        'þ' => "th".to_string(),
+        'ø' => "o".to_string(),
+        'đ' | 'ð' => "d".to_string(),
+        'ħ' => "h".to_string(),
+        'ı' => "i".to_string(),
+        'ł' => "l".to_string(),
+        'ŧ' => "t".to_string(),
+        'ς' => "σ".to_string(),
+        c => c.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::match_ranges;
+
+    #[test]
+    fn ignores_case_and_accents() {
+        assert_eq!(match_ranges("Español", "espanol"), vec![0..8]);
+        assert_eq!(match_ranges("Tiếng Việt", "viet"), vec![8..14]);
+        assert_eq!(match_ranges("Tiếng Việt", "tieng"), vec![0..7]);
+        assert_eq!(match_ranges("Ærø", "aero"), vec![0..5]);
+        assert_eq!(match_ranges("Ελληνικά", "ελληνικα"), vec![0..16]);
+    }
+
+    #[test]
+    fn precomposed_text_with_decomposed_query() {
+        assert_eq!(match_ranges("Café", "cafe\u{301}"), vec![0..5]);
+        assert_eq!(match_ranges("Café", "e\u{301}"), vec![3..5]);
+    }
+
+    #[test]
+    fn decomposed_text_with_precomposed_query() {
+        assert_eq!(match_ranges("Cafe\u{301}", "café"), vec![0..6]);
+        assert_eq!(match_ranges("Cafe\u{301}s", "És"), vec![3..7]);
+    }
+
+    #[test]
+    fn non_overlapping_repeats() {
+        assert_eq!(match_ranges("aaaa", "aa"), vec![0..2, 2..4]);
+        assert_eq!(match_ranges("ababa", "aba"), vec![0..3]);
+        assert_eq!(match_ranges("abc", ""), vec![]);
+        assert_eq!(match_ranges("abc", "abcd"), vec![]);
+    }
+
+    #[test]
+    fn byte_ranges_on_multibyte_text() {
+        let text = "日本語 Ñandú";
+        let ranges = match_ranges(text, "nandu");
+        assert_eq!(ranges, vec![10..17]);
+        assert_eq!(&text[ranges[0].clone()], "Ñandú");
+        assert_eq!(match_ranges(text, "本"), vec![3..6]);
+    }
+}
@@ -1,30 +1,248 @@
-use egui::{Color32, Id, Response, Stroke, Ui, Widget};
-use egui_material_icons::icons::{ICON_ARROW_DROP_DOWN, ICON_ARROW_DROP_UP};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
-use super::ButtonStyle;
+use egui::text::LayoutJob;
+use egui::{
+    emath::Rot2, Align, Align2, Color32, FontId, FontSelection, Id, Key, Rect, Response, RichText,
+    Rounding, Shape, Stroke, TextEdit, TextStyle, TextWrapMode, Ui, Widget, WidgetText,
+};
+use egui_material_icons::icons::ICON_CLOSE;
+
+use crate::utils::{append_highlighted, match_ranges};
+
+#[derive(Clone)]
+pub enum DropdownItem {
+    Option(DropdownOption),
+    Header(String),
+    Separator,
+}
+
+#[derive(Clone)]
+pub struct DropdownOption {
+    value: String,
+    icon: Option<String>,
+    description: Option<String>,
+    disabled: bool,
+    disabled_reason: Option<String>,
+}
+
+impl DropdownOption {
+    pub fn new(value: String) -> Self {
+        Self {
+            value,
+            icon: None,
+            description: None,
+            disabled: false,
+            disabled_reason: None,
+        }
+    }
+
+    pub fn icon(mut self, icon: String) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    pub fn description(mut self, description: String) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    pub fn disable(mut self, disable: bool) -> Self {
+        self.disabled = disable;
+        self
+    }
+
+    pub fn disabled_reason(mut self, reason: String) -> Self {
+        self.disabled = true;
+        self.disabled_reason = Some(reason);
+        self
+    }
+
+    fn layout(&self, ui: &Ui, query: &str) -> LayoutJob {
+        let mut job = LayoutJob::default();
+
+        if let Some(icon) = &self.icon {
+            RichText::new(format!("{}  ", icon)).append_to(
+                &mut job,
+                ui.style(),
+                FontSelection::Default,
+                Align::Center,
+            );
+        }
+
+        append_highlighted(&mut job, ui, &self.value, query);
+
+        if let Some(description) = &self.description {
+            RichText::new(format!("\n{}", description))
+                .small()
+                .weak()
+                .append_to(&mut job, ui.style(), FontSelection::Default, Align::Center);
+        }
+
+        job
+    }
+}
+
+impl From<DropdownOption> for DropdownItem {
+    fn from(option: DropdownOption) -> Self {
+        DropdownItem::Option(option)
+    }
+}
+
+impl From<String> for DropdownItem {
+    fn from(value: String) -> Self {
+        DropdownItem::Option(DropdownOption::new(value))
+    }
+}
+
+impl From<&str> for DropdownItem {
+    fn from(value: &str) -> Self {
+        DropdownItem::Option(DropdownOption::new(value.to_owned()))
+    }
+}
+
+#[derive(Clone)]
+pub enum OptionsState {
+    Pending,
+    Loaded(Vec<DropdownItem>),
+    Failed(String),
+}
+
+#[derive(Clone)]
+pub struct OptionSource {
+    state: Arc<Mutex<OptionsState>>,
+    retry_requested: Arc<AtomicBool>,
+}
+
+impl OptionSource {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(OptionsState::Pending)),
+            retry_requested: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn set_pending(&self) {
+        self.set_state(OptionsState::Pending);
+    }
+
+    pub fn set_loaded(&self, options: Vec<impl Into<DropdownItem>>) {
+        self.set_state(OptionsState::Loaded(
+            options.into_iter().map(Into::into).collect(),
+        ));
+    }
+
+    pub fn set_failed(&self, error: String) {
+        self.set_state(OptionsState::Failed(error));
+    }
+
+    pub fn take_retry_request(&self) -> bool {
+        self.retry_requested.swap(false, Ordering::Relaxed)
+    }
+
+    fn set_state(&self, state: OptionsState) {
+        *self.state.lock().unwrap() = state;
+    }
+
+    fn request_retry(&self) {
+        self.set_pending();
+        self.retry_requested.store(true, Ordering::Relaxed);
+    }
+
+    fn poll(&self) -> OptionsState {
+        match self.state.try_lock() {
+            Ok(state) => state.clone(),
+            Err(_) => OptionsState::Pending,
+        }
+    }
+}
+
+impl Default for OptionSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 pub struct Dropdown<'a> {
     id_source: Id,
-    options: Vec<String>,
-    selected: &'a mut String,
+    options: OptionsState,
+    source: Option<OptionSource>,
+    selected: &'a mut Option<String>,
+    placeholder: Option<String>,
+    clearable: bool,
+    searchable: bool,
+    allow_custom_value: bool,
+    max_height: f32,
+    width: Option<f32>,
+    min_width: f32,
 }
 
 impl<'a> Dropdown<'a> {
     pub fn new(
         id_source: impl std::hash::Hash,
-        options: Vec<String>,
-        selected: &'a mut String,
+        options: Vec<impl Into<DropdownItem>>,
+        selected: &'a mut Option<String>,
     ) -> Self {
-        if selected.is_empty() && !options.is_empty() {
-            *selected = options[0].clone();
-        }
-
         Self {
             id_source: Id::new(id_source),
-            options,
+            options: OptionsState::Loaded(options.into_iter().map(Into::into).collect()),
+            source: None,
             selected,
+            placeholder: None,
+            clearable: false,
+            searchable: false,
+            allow_custom_value: false,
+            max_height: 300.0,
+            width: None,
+            min_width: 0.0,
         }
     }
+
+    pub fn from_source(
+        id_source: impl std::hash::Hash,
+        source: &OptionSource,
+        selected: &'a mut Option<String>,
+    ) -> Self {
+        let mut dropdown = Self::new(id_source, Vec::<DropdownItem>::new(), selected);
+        dropdown.options = source.poll();
+        dropdown.source = Some(source.clone());
+        dropdown
+    }
+
+    pub fn placeholder(mut self, placeholder: String) -> Self {
+        self.placeholder = Some(placeholder);
+        self
+    }
+
+    pub fn clearable(mut self, clearable: bool) -> Self {
+        self.clearable = clearable;
+        self
+    }
+
+    pub fn searchable(mut self, searchable: bool) -> Self {
+        self.searchable = searchable;
+        self
+    }
+
+    pub fn allow_custom_value(mut self, allow_custom_value: bool) -> Self {
+        self.allow_custom_value = allow_custom_value;
+        self
+    }
+
+    pub fn max_height(mut self, max_height: f32) -> Self {
+        self.max_height = max_height;
+        self
+    }
+
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn min_width(mut self, min_width: f32) -> Self {
+        self.min_width = min_width;
+        self
+    }
 }
 
 impl<'a> Widget for Dropdown<'a> {
@@ -32,77 +250,313 @@ impl<'a> Widget for Dropdown<'a> {
         let Dropdown {
             id_source,
             options,
+            source,
             selected,
+            placeholder,
+            clearable,
+            searchable,
+            allow_custom_value,
+            max_height,
+            width,
+            min_width,
         } = self;
 
         let button_id = ui.make_persistent_id(id_source);
         let popup_id = button_id.with("popup");
+        let search_id = popup_id.with("search");
 
         let is_open = ui.memory(|m| m.is_popup_open(popup_id));
-        let icon = if is_open {
-            ICON_ARROW_DROP_UP
+        let openness = ui.ctx().animate_bool_responsive(popup_id, is_open);
+
+        let padding = ui.spacing().button_padding * 4.0;
+        let caret_width = 10.0;
+        let clear_width = 16.0;
+        let show_clear = clearable && selected.is_some();
+        let trailing_width = if show_clear {
+            caret_width + clear_width + 2.0 * padding.x
         } else {
-            ICON_ARROW_DROP_DOWN
+            caret_width + padding.x
         };
 
-        let button_text = format!("{}     {}", selected, icon);
-        let mut button_response = ui.add(
-            crate::Button::new(
-                button_text,
-                super::ButtonVariant::Custom(Color32::from_rgb(117, 117, 117)),
-                super::ButtonSize::Large,
-                true,
-                false,
+        let (label, is_placeholder) = match selected.as_deref() {
+            Some(value) => (value.to_owned(), false),
+            None => (placeholder.unwrap_or_default(), true),
+        };
+        let natural_width = WidgetText::from(label.as_str())
+            .into_galley(
+                ui,
+                Some(TextWrapMode::Extend),
+                f32::INFINITY,
+                TextStyle::Heading,
             )
-            .text_color(Color32::BLACK)
-            .on_hover_style(ButtonStyle::new(
-                Color32::TRANSPARENT,
-                Stroke::new(1.2, Color32::from_rgb(0, 123, 187)),
-                Color32::BLACK,
-            )),
+            .size()
+            .x
+            + trailing_width
+            + 2.0 * padding.x;
+        let width = width
+            .unwrap_or_else(|| natural_width.clamp(min_width, ui.available_width().max(min_width)));
+
+        let galley = WidgetText::from(label).into_galley(
+            ui,
+            Some(TextWrapMode::Truncate),
+            width - trailing_width - 2.0 * padding.x,
+            TextStyle::Heading,
+        );
+        let height = galley
+            .size()
+            .y
+            .max(ui.text_style_height(&TextStyle::Heading))
+            + 2.0 * padding.y;
+
+        let (rect, mut button_response) =
+            ui.allocate_exact_size(egui::vec2(width, height), egui::Sense::click());
+
+        let caret_rect = Rect::from_center_size(
+            egui::pos2(
+                rect.right() - padding.x - caret_width / 2.0,
+                rect.center().y,
+            ),
+            egui::vec2(caret_width, caret_width),
+        );
+        let clear_rect = Rect::from_center_size(
+            egui::pos2(
+                caret_rect.left() - padding.x - clear_width / 2.0,
+                rect.center().y,
+            ),
+            egui::vec2(clear_width, rect.height()),
         );
+        let clear_response = show_clear
+            .then(|| ui.interact(clear_rect, button_id.with("clear"), egui::Sense::click()));
+
+        if ui.is_rect_visible(rect) {
+            let (rect, mut stroke) = if button_response.hovered() {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                (
+                    rect.expand(1.0),
+                    Stroke::new(1.2, Color32::from_rgb(0, 123, 187)),
+                )
+            } else {
+                (rect, Stroke::new(1.0, Color32::from_rgb(117, 117, 117)))
+            };
+            let mut text_color = Color32::BLACK;
+
+            if button_response.is_pointer_button_down_on() {
+                stroke.color = stroke.color.linear_multiply(0.75);
+                text_color = text_color.linear_multiply(0.75);
+            }
+
+            ui.painter().rect_stroke(rect, Rounding::same(4.0), stroke);
+
+            let label_color = if is_placeholder {
+                Color32::from_rgb(117, 117, 117)
+            } else {
+                text_color
+            };
+            let text_pos = egui::pos2(
+                rect.left() + padding.x,
+                rect.center().y - galley.size().y / 2.0,
+            );
+            ui.painter().galley(text_pos, galley, label_color);
+
+            if let Some(clear_response) = &clear_response {
+                let clear_color = if clear_response.hovered() {
+                    ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                    Color32::from_rgb(0, 123, 187)
+                } else {
+                    Color32::from_rgb(117, 117, 117)
+                };
+                ui.painter().text(
+                    clear_rect.center(),
+                    Align2::CENTER_CENTER,
+                    ICON_CLOSE,
+                    FontId::proportional(clear_width),
+                    clear_color,
+                );
+            }
+
+            paint_caret(ui, caret_rect, openness, text_color);
+        }
 
         let mut selected_changed = false;
+        let mut just_opened = false;
+
+        if clear_response.is_some_and(|response| response.clicked()) {
+            *selected = None;
+            selected_changed = true;
+        }
 
         if button_response.clicked() {
             ui.memory_mut(|m| m.toggle_popup(popup_id));
+            just_opened = ui.memory(|m| m.is_popup_open(popup_id));
+            if just_opened {
+                ui.data_mut(|d| d.remove::<String>(search_id));
+                request_scroll_to_selected(ui, popup_id);
+            }
         }
 
-        if ui.memory(|m| m.is_popup_open(popup_id)) {
-            let popup_width = button_response.rect.width();
-            let popup_position = button_response.rect.left_bottom() + egui::vec2(-6.0, 2.0);
-
-            let area_response = egui::Area::new(popup_id)
-                .order(egui::Order::Foreground)
-                .fixed_pos(popup_position)
-                .show(ui.ctx(), |ui| {
-                    ui.set_min_width(popup_width);
-                    egui::Frame::popup(ui.style())
-                        .stroke(Stroke::NONE)
-                        .show(ui, |ui| {
-                            ui.set_min_width(popup_width);
-                            for option in &options {
-                                let is_selected = *option == *selected;
-                                let response = ui.add_sized(
-                                    [popup_width, 0.0],
-                                    egui::SelectableLabel::new(is_selected, option),
-                                );
-                                if response.clicked() {
-                                    *selected = option.clone();
-                                    selected_changed = true;
-                                    ui.memory_mut(|m| m.close_popup());
+        show_popup(
+            ui,
+            popup_id,
+            &button_response,
+            max_height,
+            |ui, popup_width| {
+                let options = match &options {
+                    OptionsState::Pending => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(RichText::new("Loading…").weak());
+                        });
+                        return;
+                    }
+                    OptionsState::Failed(error) => {
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+                            if let Some(source) = &source {
+                                if ui.small_button("Retry").clicked() {
+                                    source.request_retry();
                                 }
                             }
                         });
+                        return;
+                    }
+                    OptionsState::Loaded(options) => options,
+                };
+
+                let mut query = String::new();
+                let mut submitted = false;
+
+                if searchable {
+                    query = ui.data(|d| d.get_temp(search_id)).unwrap_or_default();
+                    let search_response = ui.add(
+                        TextEdit::singleline(&mut query)
+                            .hint_text("Search…")
+                            .desired_width(popup_width),
+                    );
+                    if just_opened {
+                        search_response.request_focus();
+                    }
+                    submitted =
+                        search_response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+                    ui.data_mut(|d| d.insert_temp(search_id, query.clone()));
+                }
+
+                let query = query.trim();
+                let mut visible: Vec<&DropdownItem> = Vec::new();
+                let mut pending_header = None;
+                for item in options {
+                    match item {
+                        DropdownItem::Header(_) => pending_header = Some(item),
+                        DropdownItem::Separator => {
+                            if query.is_empty() {
+                                visible.extend(pending_header.take());
+                                visible.push(item);
+                            }
+                        }
+                        DropdownItem::Option(option) => {
+                            if query.is_empty() || !match_ranges(&option.value, query).is_empty() {
+                                visible.extend(pending_header.take());
+                                visible.push(item);
+                            }
+                        }
+                    }
+                }
+
+                let enabled: Vec<&DropdownOption> = visible
+                    .iter()
+                    .filter_map(|item| match item {
+                        DropdownItem::Option(option) if !option.disabled => Some(option),
+                        _ => None,
+                    })
+                    .collect();
+                let exact_match = visible.iter().find_map(|item| match item {
+                    DropdownItem::Option(option)
+                        if match_ranges(&option.value, query)
+                            .first()
+                            .is_some_and(|range| *range == (0..option.value.len())) =>
+                    {
+                        Some(option)
+                    }
+                    _ => None,
                 });
+                let offer_custom = allow_custom_value && !query.is_empty() && exact_match.is_none();
 
-            if !button_response.clicked()
-                && (ui.input(|i| i.key_pressed(egui::Key::Escape))
-                    || area_response.response.clicked_elsewhere())
-            {
-                ui.memory_mut(|m| m.close_popup());
-            }
-        }
+                let mut commit = egui::ScrollArea::vertical()
+                    .auto_shrink([false, true])
+                    .show(ui, |ui| {
+                        let mut commit = None;
+
+                        for item in &visible {
+                            match item {
+                                DropdownItem::Header(title) => {
+                                    ui.label(RichText::new(title).small().weak());
+                                }
+                                DropdownItem::Separator => {
+                                    ui.separator();
+                                }
+                                DropdownItem::Option(option) => {
+                                    let is_selected =
+                                        selected.as_deref() == Some(option.value.as_str());
+                                    let response = ui
+                                        .add_enabled_ui(!option.disabled, |ui| {
+                                            ui.add_sized(
+                                                [popup_width, 0.0],
+                                                egui::SelectableLabel::new(
+                                                    is_selected,
+                                                    option.layout(ui, query),
+                                                ),
+                                            )
+                                        })
+                                        .inner;
+                                    let response = match &option.disabled_reason {
+                                        Some(reason) => response.on_disabled_hover_text(reason),
+                                        None => response,
+                                    };
+                                    if is_selected {
+                                        scroll_to_selected_on_open(ui, popup_id, &response);
+                                    }
+                                    if response.clicked() {
+                                        commit = Some(option.value.clone());
+                                    }
+                                }
+                            }
+                        }
+
+                        if offer_custom {
+                            let response = ui.add_sized(
+                                [popup_width, 0.0],
+                                egui::SelectableLabel::new(false, format!("Use \"{}\"", query)),
+                            );
+                            if response.clicked() {
+                                commit = Some(query.to_owned());
+                            }
+                        } else if visible.is_empty() && !query.is_empty() {
+                            ui.add_sized(
+                                [popup_width, 0.0],
+                                egui::Label::new(RichText::new("No matches").weak()),
+                            );
+                        }
+
+                        commit
+                    })
+                    .inner;
+
+                if submitted && commit.is_none() {
+                    commit = match exact_match {
+                        Some(option) if !option.disabled => Some(option.value.clone()),
+                        Some(_) => None,
+                        None if offer_custom => Some(query.to_owned()),
+                        None if enabled.len() == 1 => Some(enabled[0].value.clone()),
+                        None => None,
+                    };
+                }
+
+                if let Some(value) = commit {
+                    *selected = Some(value);
+                    selected_changed = true;
+                    ui.memory_mut(|m| m.close_popup());
+                }
+            },
+        );
 
         if button_response.has_focus() {
             ui.painter()
@@ -117,3 +571,103 @@ impl<'a> Widget for Dropdown<'a> {
         }
     }
 }
+
+pub(crate) fn paint_caret(ui: &Ui, rect: Rect, openness: f32, color: Color32) {
+    let rotation = Rot2::from_angle(openness * std::f32::consts::PI);
+    let half = rect.width() / 2.0;
+    let points = [
+        egui::vec2(-half, -half / 2.0),
+        egui::vec2(half, -half / 2.0),
+        egui::vec2(0.0, half / 2.0),
+    ]
+    .iter()
+    .map(|point| rect.center() + rotation * *point)
+    .collect();
+
+    ui.painter()
+        .add(Shape::convex_polygon(points, color, Stroke::NONE));
+}
+
+pub(crate) fn show_popup<R>(
+    ui: &Ui,
+    popup_id: Id,
+    button_response: &Response,
+    max_height: f32,
+    add_contents: impl FnOnce(&mut Ui, f32) -> R,
+) -> Option<R> {
+    if !ui.memory(|m| m.is_popup_open(popup_id)) {
+        return None;
+    }
+
+    let button_rect = button_response.rect;
+    let screen_rect = ui.ctx().screen_rect();
+    let popup_width = button_rect.width();
+    let offset = egui::vec2(-6.0, 2.0);
+
+    let frame = egui::Frame::popup(ui.style()).stroke(Stroke::NONE);
+    let frame_size = frame.total_margin().sum();
+    let last_rect = ui.memory(|m| m.area_rect(popup_id));
+    let expected_size = last_rect
+        .map_or(egui::vec2(popup_width, max_height) + frame_size, |rect| {
+            rect.size()
+        });
+
+    let space_below = screen_rect.bottom() - button_rect.bottom() - offset.y;
+    let space_above = button_rect.top() - screen_rect.top() - offset.y;
+    let open_above = expected_size.y > space_below && space_above > space_below;
+
+    let (pivot, mut popup_position, available_height) = if open_above {
+        (
+            Align2::LEFT_BOTTOM,
+            button_rect.left_top() + egui::vec2(offset.x, -offset.y),
+            space_above,
+        )
+    } else {
+        (
+            Align2::LEFT_TOP,
+            button_rect.left_bottom() + offset,
+            space_below,
+        )
+    };
+    popup_position.x = popup_position.x.clamp(
+        screen_rect.left(),
+        (screen_rect.right() - expected_size.x).max(screen_rect.left()),
+    );
+    let content_max_height = max_height.min(available_height - frame_size.y).max(0.0);
+
+    let area_response = egui::Area::new(popup_id)
+        .order(egui::Order::Foreground)
+        .pivot(pivot)
+        .fixed_pos(popup_position)
+        .show(ui.ctx(), |ui| {
+            ui.set_min_width(popup_width);
+            frame
+                .show(ui, |ui| {
+                    ui.set_min_width(popup_width);
+                    ui.set_max_width(popup_width);
+                    ui.set_max_height(content_max_height);
+                    add_contents(ui, popup_width)
+                })
+                .inner
+        });
+
+    if !button_response.clicked()
+        && (ui.input(|i| i.key_pressed(egui::Key::Escape))
+            || area_response.response.clicked_elsewhere())
+    {
+        ui.memory_mut(|m| m.close_popup());
+    }
+
+    Some(area_response.inner)
+}
+
+pub(crate) fn scroll_to_selected_on_open(ui: &Ui, popup_id: Id, response: &Response) {
+    let scroll_id = popup_id.with("scroll_to_selected");
+    if !ui.is_sizing_pass() && ui.data_mut(|d| d.remove_temp::<bool>(scroll_id)).is_some() {
+        response.scroll_to_me(Some(Align::Center));
+    }
+}
+
+pub(crate) fn request_scroll_to_selected(ui: &Ui, popup_id: Id) {
+    ui.data_mut(|d| d.insert_temp(popup_id.with("scroll_to_selected"), true));
+}
@@ -0,0 +1,308 @@
+use std::ops::RangeInclusive;
+
+use egui::{
+    emath::Numeric, pos2, vec2, Align, Align2, Color32, DragValue, FontId, Layout, Margin, Rect,
+    Response, Rounding, Sense, Slider, Stroke, TextEdit, TextStyle, Ui, Widget,
+};
+use egui_material_icons::icons::{ICON_ADD, ICON_ERROR, ICON_REMOVE};
+
+const ERROR_COLOR: Color32 = Color32::from_rgb(220, 53, 69);
+
+fn editor_height(ui: &Ui) -> f32 {
+    ui.spacing().interact_size.y * 1.4
+}
+
+fn step_decimals(step: f64) -> usize {
+    (0..6)
+        .find(|&decimals| {
+            let scaled = step * 10f64.powi(decimals as i32);
+            (scaled - scaled.round()).abs() < 1e-9 * scaled.abs().max(1.0)
+        })
+        .unwrap_or(6)
+}
+
+type Validator<'a> = Box<dyn Fn(&str) -> Result<(), String> + 'a>;
+
+pub struct TextField<'a> {
+    text: &'a mut String,
+    hint: Option<String>,
+    width: f32,
+    validator: Option<Validator<'a>>,
+}
+
+impl<'a> TextField<'a> {
+    pub fn new(text: &'a mut String) -> Self {
+        Self {
+            text,
+            hint: None,
+            width: 160.0,
+            validator: None,
+        }
+    }
+
+    pub fn hint(mut self, hint: String) -> Self {
+        self.hint = Some(hint);
+        self
+    }
+
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn validate(mut self, validator: impl Fn(&str) -> Result<(), String> + 'a) -> Self {
+        self.validator = Some(Box::new(validator));
+        self
+    }
+}
+
+impl<'a> Widget for TextField<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let height = editor_height(ui);
+        let mut edit = TextEdit::singleline(self.text)
+            .vertical_align(Align::Center)
+            .min_size(vec2(self.width, height))
+            .desired_width(self.width);
+        if let Some(hint) = self.hint {
+            edit = edit.hint_text(hint);
+        }
+        if self.validator.is_some() {
+            let icon_width = TextStyle::Body.resolve(ui.style()).size;
+            edit = edit.margin(Margin {
+                left: 4.0,
+                right: 8.0 + icon_width,
+                top: 2.0,
+                bottom: 2.0,
+            });
+        }
+        let response = ui.add(edit);
+
+        let error = match &self.validator {
+            Some(validator) => validator(self.text).err(),
+            None => None,
+        };
+
+        match error {
+            Some(error) => {
+                let rect = response.rect;
+                let rounding = ui.visuals().widgets.inactive.rounding;
+                ui.painter()
+                    .rect_stroke(rect, rounding, Stroke::new(1.2, ERROR_COLOR));
+                ui.painter().text(
+                    pos2(rect.right() - 4.0, rect.center().y),
+                    Align2::RIGHT_CENTER,
+                    ICON_ERROR,
+                    FontId::proportional(TextStyle::Body.resolve(ui.style()).size),
+                    ERROR_COLOR,
+                );
+                response.on_hover_text(error)
+            }
+            None => response,
+        }
+    }
+}
+
+pub struct Stepper<'a, N: Numeric> {
+    value: &'a mut N,
+    range: RangeInclusive<N>,
+    step: f64,
+    width: f32,
+}
+
+impl<'a, N: Numeric> Stepper<'a, N> {
+    pub fn new(value: &'a mut N, range: RangeInclusive<N>) -> Self {
+        Self {
+            value,
+            range,
+            step: 1.0,
+            width: 120.0,
+        }
+    }
+
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = step;
+        self
+    }
+
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+}
+
+impl<'a, N: Numeric> Widget for Stepper<'a, N> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Stepper {
+            value,
+            range,
+            step,
+            width,
+        } = self;
+        let height = editor_height(ui);
+        let (rect, mut response) = ui.allocate_exact_size(vec2(width, height), Sense::hover());
+
+        let min = range.start().to_f64();
+        let max = range.end().to_f64();
+        let current = value.to_f64();
+
+        let minus_rect = Rect::from_min_size(rect.min, vec2(height, height));
+        let plus_rect = Rect::from_min_size(
+            pos2(rect.right() - height, rect.top()),
+            vec2(height, height),
+        );
+        let value_rect = Rect::from_min_max(minus_rect.right_top(), plus_rect.left_bottom());
+
+        let visuals = ui.visuals().widgets.inactive;
+        ui.painter().rect(
+            rect,
+            visuals.rounding,
+            ui.visuals().extreme_bg_color,
+            visuals.bg_stroke,
+        );
+
+        let mut stepped = None;
+        for (button_rect, icon, delta, enabled) in [
+            (minus_rect, ICON_REMOVE, -step, current > min),
+            (plus_rect, ICON_ADD, step, current < max),
+        ] {
+            let enabled = enabled && ui.is_enabled();
+            let sense = if enabled {
+                Sense::click()
+            } else {
+                Sense::hover()
+            };
+            let button_response = ui.interact(button_rect, response.id.with(icon), sense);
+            let style = ui.style().interact(&button_response);
+            if enabled && button_response.hovered() {
+                ui.painter().rect_filled(
+                    button_rect.shrink(2.0),
+                    Rounding::same(visuals.rounding.nw),
+                    style.weak_bg_fill,
+                );
+            }
+            let color = if enabled {
+                style.text_color()
+            } else {
+                ui.visuals().weak_text_color()
+            };
+            ui.painter().text(
+                button_rect.center(),
+                Align2::CENTER_CENTER,
+                icon,
+                FontId::proportional(TextStyle::Body.resolve(ui.style()).size),
+                color,
+            );
+            if button_response.clicked() {
+                stepped = Some((current + delta).clamp(min, max));
+            }
+            response = response.union(button_response);
+        }
+
+        if let Some(stepped) = stepped {
+            *value = N::from_f64(stepped);
+            response.mark_changed();
+        }
+
+        let drag_response = ui.put(
+            value_rect,
+            DragValue::new(value)
+                .range(range)
+                .speed(step)
+                .update_while_editing(false),
+        );
+        response.union(drag_response)
+    }
+}
+
+pub struct ValueSlider<'a, N: Numeric> {
+    value: &'a mut N,
+    range: RangeInclusive<N>,
+    step: Option<f64>,
+    suffix: String,
+    width: f32,
+}
+
+impl<'a, N: Numeric> ValueSlider<'a, N> {
+    pub fn new(value: &'a mut N, range: RangeInclusive<N>) -> Self {
+        Self {
+            value,
+            range,
+            step: None,
+            suffix: String::new(),
+            width: 120.0,
+        }
+    }
+
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = Some(step);
+        self
+    }
+
+    pub fn suffix(mut self, suffix: String) -> Self {
+        self.suffix = suffix;
+        self
+    }
+
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+}
+
+impl<'a, N: Numeric> Widget for ValueSlider<'a, N> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let ValueSlider {
+            value,
+            range,
+            step,
+            suffix,
+            width,
+        } = self;
+        let height = editor_height(ui);
+
+        let decimals = if N::INTEGRAL {
+            0
+        } else {
+            step.map_or(2, step_decimals)
+        };
+        let format = |value: f64| format!("{:.*}{}", decimals, value, suffix);
+
+        let font_id = TextStyle::Body.resolve(ui.style());
+        let readout_width = [range.start().to_f64(), range.end().to_f64()]
+            .into_iter()
+            .map(|bound| {
+                ui.painter()
+                    .layout_no_wrap(format(bound), font_id.clone(), Color32::PLACEHOLDER)
+                    .size()
+                    .x
+            })
+            .fold(0.0, f32::max);
+        let spacing = ui.spacing().item_spacing.x;
+
+        ui.allocate_ui_with_layout(
+            vec2(width + spacing + readout_width, height),
+            Layout::left_to_right(Align::Center),
+            |ui| {
+                ui.set_min_height(height);
+                ui.spacing_mut().slider_width = width;
+                let mut slider = Slider::new(&mut *value, range).show_value(false);
+                if let Some(step) = step {
+                    slider = slider.step_by(step);
+                }
+                let response = ui.add(slider);
+
+                let (readout_rect, _) =
+                    ui.allocate_exact_size(vec2(readout_width, height), Sense::hover());
+                ui.painter().text(
+                    readout_rect.right_center(),
+                    Align2::RIGHT_CENTER,
+                    format(value.to_f64()),
+                    font_id,
+                    ui.visuals().text_color(),
+                );
+                response
+            },
+        )
+        .inner
+    }
+}
@@ -2,14 +2,24 @@ mod buttons;
 mod cards;
 mod dropdowns;
 mod exit_selector;
+mod inline_editors;
+mod multi_dropdowns;
 mod settings_lines;
+mod settings_pages;
+mod settings_sections;
 mod switches;
 mod tab_bars;
+mod tab_views;
 
 pub use buttons::*;
 pub use cards::*;
 pub use dropdowns::*;
 pub use exit_selector::*;
+pub use inline_editors::*;
+pub use multi_dropdowns::*;
 pub use settings_lines::*;
+pub use settings_pages::*;
+pub use settings_sections::*;
 pub use switches::*;
 pub use tab_bars::*;
+pub use tab_views::*;
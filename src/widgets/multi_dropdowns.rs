@@ -0,0 +1,303 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::hash::Hash;
+
+use egui::{
+    pos2, vec2, Align2, Color32, FontId, Id, Rect, Response, Rounding, Sense, Stroke, TextStyle,
+    Ui, Widget,
+};
+use egui_material_icons::icons::ICON_CLOSE;
+
+use super::dropdowns::{
+    paint_caret, request_scroll_to_selected, scroll_to_selected_on_open, show_popup,
+};
+
+pub trait MultiSelection<T> {
+    fn contains_item(&self, item: &T) -> bool;
+    fn insert_item(&mut self, item: T);
+    fn remove_item(&mut self, item: &T);
+    fn clear_items(&mut self);
+}
+
+impl<T: PartialEq> MultiSelection<T> for Vec<T> {
+    fn contains_item(&self, item: &T) -> bool {
+        self.contains(item)
+    }
+
+    fn insert_item(&mut self, item: T) {
+        if !self.contains(&item) {
+            self.push(item);
+        }
+    }
+
+    fn remove_item(&mut self, item: &T) {
+        self.retain(|existing| existing != item);
+    }
+
+    fn clear_items(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: Eq + Hash> MultiSelection<T> for HashSet<T> {
+    fn contains_item(&self, item: &T) -> bool {
+        self.contains(item)
+    }
+
+    fn insert_item(&mut self, item: T) {
+        self.insert(item);
+    }
+
+    fn remove_item(&mut self, item: &T) {
+        self.remove(item);
+    }
+
+    fn clear_items(&mut self) {
+        self.clear();
+    }
+}
+
+pub struct MultiDropdown<'a, T, S: MultiSelection<T>> {
+    id_source: Id,
+    options: Vec<T>,
+    selected: &'a mut S,
+    width: f32,
+    max_height: f32,
+}
+
+impl<'a, T, S> MultiDropdown<'a, T, S>
+where
+    T: Clone + Display,
+    S: MultiSelection<T>,
+{
+    pub fn new(id_source: impl std::hash::Hash, options: Vec<T>, selected: &'a mut S) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            options,
+            selected,
+            width: 240.0,
+            max_height: 300.0,
+        }
+    }
+
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn max_height(mut self, max_height: f32) -> Self {
+        self.max_height = max_height;
+        self
+    }
+}
+
+impl<'a, T, S> Widget for MultiDropdown<'a, T, S>
+where
+    T: Clone + Display,
+    S: MultiSelection<T>,
+{
+    fn ui(self, ui: &mut Ui) -> Response {
+        let MultiDropdown {
+            id_source,
+            options,
+            selected,
+            width,
+            max_height,
+        } = self;
+
+        let button_id = ui.make_persistent_id(id_source);
+        let popup_id = button_id.with("popup");
+
+        let padding = 8.0;
+        let chip_spacing = 4.0;
+        let height = ui.spacing().interact_size.y * 1.8;
+        let (rect, mut button_response) =
+            ui.allocate_exact_size(vec2(width, height), Sense::click());
+
+        let is_open = ui.memory(|m| m.is_popup_open(popup_id));
+        let openness = ui.ctx().animate_bool_responsive(popup_id, is_open);
+
+        let text_color = ui.visuals().text_color();
+        let chip_fill = ui.visuals().widgets.inactive.bg_fill;
+        let font_id = TextStyle::Body.resolve(ui.style());
+        let icon_font_id = FontId::proportional(font_id.size);
+
+        let caret_width = 10.0;
+        let caret_rect = Rect::from_center_size(
+            pos2(rect.right() - padding - caret_width / 2.0, rect.center().y),
+            vec2(caret_width, caret_width),
+        );
+        let chips_right = caret_rect.left() - chip_spacing;
+
+        let selected_options: Vec<&T> = options
+            .iter()
+            .filter(|option| selected.contains_item(option))
+            .collect();
+
+        let mut selected_changed = false;
+        let mut removed = None;
+        let mut x = rect.left() + padding;
+        let chip_height = height - 2.0 * padding;
+
+        for (index, option) in selected_options.iter().enumerate() {
+            let label_galley =
+                ui.painter()
+                    .layout_no_wrap(option.to_string(), font_id.clone(), text_color);
+            let close_galley = ui.painter().layout_no_wrap(
+                ICON_CLOSE.to_owned(),
+                icon_font_id.clone(),
+                text_color,
+            );
+            let chip_width = label_galley.size().x + close_galley.size().x + 3.0 * chip_spacing;
+
+            let hidden_after = selected_options.len() - index - 1;
+            let overflow_width = if hidden_after > 0 {
+                ui.painter()
+                    .layout_no_wrap(format!("+{}", hidden_after), font_id.clone(), text_color)
+                    .size()
+                    .x
+                    + chip_spacing
+            } else {
+                0.0
+            };
+
+            if x + chip_width + overflow_width > chips_right {
+                ui.painter().text(
+                    pos2(x, rect.center().y),
+                    Align2::LEFT_CENTER,
+                    format!("+{}", selected_options.len() - index),
+                    font_id.clone(),
+                    text_color,
+                );
+                break;
+            }
+
+            let chip_rect = Rect::from_min_size(
+                pos2(x, rect.center().y - chip_height / 2.0),
+                vec2(chip_width, chip_height),
+            );
+            let close_rect = Rect::from_min_max(
+                pos2(
+                    chip_rect.right() - close_galley.size().x - chip_spacing,
+                    chip_rect.top(),
+                ),
+                chip_rect.right_bottom(),
+            );
+            let close_response =
+                ui.interact(close_rect, button_id.with(("chip", index)), Sense::click());
+            if close_response.clicked() {
+                removed = Some((*option).clone());
+            }
+
+            let close_color = if close_response.hovered() {
+                ui.visuals().strong_text_color()
+            } else {
+                ui.visuals().weak_text_color()
+            };
+
+            ui.painter()
+                .rect_filled(chip_rect, Rounding::same(chip_height / 2.0), chip_fill);
+            ui.painter().galley(
+                pos2(
+                    chip_rect.left() + chip_spacing,
+                    chip_rect.center().y - label_galley.size().y / 2.0,
+                ),
+                label_galley,
+                text_color,
+            );
+            ui.painter().text(
+                close_rect.center(),
+                Align2::CENTER_CENTER,
+                ICON_CLOSE,
+                icon_font_id.clone(),
+                close_color,
+            );
+
+            x = chip_rect.right() + chip_spacing;
+        }
+
+        if selected_options.is_empty() {
+            ui.painter().text(
+                pos2(x, rect.center().y),
+                Align2::LEFT_CENTER,
+                "Select…",
+                font_id.clone(),
+                ui.visuals().weak_text_color(),
+            );
+        }
+
+        if let Some(option) = removed {
+            selected.remove_item(&option);
+            selected_changed = true;
+        } else if button_response.clicked() {
+            ui.memory_mut(|m| m.toggle_popup(popup_id));
+            if ui.memory(|m| m.is_popup_open(popup_id)) {
+                request_scroll_to_selected(ui, popup_id);
+            }
+        }
+
+        let stroke = if button_response.hovered() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+            Stroke::new(1.2, Color32::from_rgb(0, 123, 187))
+        } else {
+            Stroke::new(1.0, Color32::from_rgb(117, 117, 117))
+        };
+        ui.painter().rect_stroke(rect, Rounding::same(4.0), stroke);
+        paint_caret(ui, caret_rect, openness, text_color);
+
+        show_popup(
+            ui,
+            popup_id,
+            &button_response,
+            max_height,
+            |ui, _popup_width| {
+                ui.horizontal(|ui| {
+                    if ui.small_button("Select all").clicked() {
+                        for option in &options {
+                            if !selected.contains_item(option) {
+                                selected.insert_item(option.clone());
+                                selected_changed = true;
+                            }
+                        }
+                    }
+                    if ui.small_button("Clear").clicked() {
+                        selected.clear_items();
+                        selected_changed = true;
+                    }
+                });
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .auto_shrink([false, true])
+                    .show(ui, |ui| {
+                        for option in &options {
+                            let mut checked = selected.contains_item(option);
+                            let response = ui.checkbox(&mut checked, option.to_string());
+                            if checked {
+                                scroll_to_selected_on_open(ui, popup_id, &response);
+                            }
+                            if response.changed() {
+                                if checked {
+                                    selected.insert_item(option.clone());
+                                } else {
+                                    selected.remove_item(option);
+                                }
+                                selected_changed = true;
+                            }
+                        }
+                    });
+            },
+        );
+
+        if button_response.has_focus() {
+            ui.painter()
+                .rect_stroke(button_response.rect, 0.0, Stroke::new(2.0, Color32::BLUE));
+        }
+
+        if selected_changed {
+            button_response.mark_changed();
+        }
+
+        button_response
+    }
+}
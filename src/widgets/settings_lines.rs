@@ -1,9 +1,21 @@
-use egui::{Layout, Response, Ui, Widget};
+use egui::{Layout, Response, RichText, Rounding, Sense, Shape, TextStyle, Ui, Widget};
+use egui_material_icons::icons::ICON_CHEVRON_RIGHT;
+
+use crate::utils::highlight_matches;
+
+enum Trailing<'a> {
+    Widget(Box<dyn FnOnce(&mut Ui) -> Response + 'a>),
+    Navigation(Option<String>),
+    Value(String),
+}
 
 pub struct SettingsLine<'a> {
     icon: String,
     label: String,
-    switch: Box<dyn FnOnce(&mut Ui) -> Response + 'a>,
+    subtitle: Option<String>,
+    trailing: Trailing<'a>,
+    highlight: Option<String>,
+    disabled: bool,
 }
 
 impl<'a> SettingsLine<'a> {
@@ -11,21 +23,130 @@ impl<'a> SettingsLine<'a> {
         Self {
             icon,
             label,
-            switch: Box::new(switch),
+            subtitle: None,
+            trailing: Trailing::Widget(Box::new(switch)),
+            highlight: None,
+            disabled: false,
+        }
+    }
+
+    pub fn navigation(icon: String, label: String, value: Option<String>) -> Self {
+        Self {
+            icon,
+            label,
+            subtitle: None,
+            trailing: Trailing::Navigation(value),
+            highlight: None,
+            disabled: false,
+        }
+    }
+
+    pub fn value(icon: String, label: String, value: String) -> Self {
+        Self {
+            icon,
+            label,
+            subtitle: None,
+            trailing: Trailing::Value(value),
+            highlight: None,
+            disabled: false,
         }
     }
+
+    pub fn subtitle(mut self, subtitle: String) -> Self {
+        self.subtitle = Some(subtitle);
+        self
+    }
+
+    pub fn highlight(mut self, query: String) -> Self {
+        self.highlight = Some(query);
+        self
+    }
+
+    pub fn disable(mut self, disable: bool) -> Self {
+        self.disabled = disable;
+        self
+    }
 }
 
 impl<'a> Widget for SettingsLine<'a> {
     fn ui(self, ui: &mut Ui) -> Response {
-        ui.horizontal(|ui| {
-            ui.heading(&self.icon);
-            ui.heading(&self.label);
+        let SettingsLine {
+            icon,
+            label,
+            subtitle,
+            trailing,
+            highlight: query,
+            disabled,
+        } = self;
+
+        let row_id = ui.id().with(("settings_line", &label));
+        let enabled = !disabled && ui.is_enabled();
+        let sense = match trailing {
+            Trailing::Navigation(_) if enabled => Sense::click(),
+            _ => Sense::hover(),
+        };
+        let highlight = ui.painter().add(Shape::Noop);
 
-            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
-                (self.switch)(ui)
+        let inner = ui.add_enabled_ui(!disabled, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(&icon).text_style(TextStyle::Heading));
+                ui.vertical(|ui| {
+                    match &query {
+                        Some(query) => ui.label(highlight_matches(ui, &label, query)),
+                        None => ui.label(RichText::new(&label).text_style(TextStyle::Body)),
+                    };
+                    if let Some(subtitle) = &subtitle {
+                        ui.label(RichText::new(subtitle).small().weak());
+                    }
+                });
+
+                ui.with_layout(
+                    Layout::right_to_left(egui::Align::Center),
+                    |ui| match trailing {
+                        Trailing::Widget(widget) => Some(widget(ui)),
+                        Trailing::Navigation(value) => {
+                            ui.label(
+                                RichText::new(ICON_CHEVRON_RIGHT)
+                                    .text_style(TextStyle::Heading)
+                                    .weak(),
+                            );
+                            if let Some(value) = value {
+                                ui.label(RichText::new(value).weak());
+                            }
+                            None
+                        }
+                        Trailing::Value(value) => {
+                            ui.label(RichText::new(value).weak());
+                            None
+                        }
+                    },
+                )
+                .inner
             })
-        })
-        .response
+        });
+
+        let row_rect = inner.response.rect;
+        let mut response = ui.interact(row_rect, row_id, sense);
+
+        if enabled && response.hovered() {
+            let fill = if response.is_pointer_button_down_on() && sense.click {
+                ui.visuals().widgets.active.weak_bg_fill
+            } else {
+                ui.visuals().widgets.hovered.weak_bg_fill
+            };
+            if sense.click {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+            }
+            ui.painter().set(
+                highlight,
+                Shape::rect_filled(row_rect.expand(4.0), Rounding::same(4.0), fill),
+            );
+        }
+
+        if let Some(trailing_response) = inner.inner.inner {
+            response = response.union(trailing_response);
+        }
+
+        response
     }
 }
@@ -0,0 +1,302 @@
+use std::ops::RangeInclusive;
+
+use egui::{emath::Numeric, Id, Response, RichText, TextEdit, Ui};
+use egui_material_icons::icons::{ICON_SEARCH, ICON_SEARCH_OFF};
+
+use super::{
+    Dropdown, DropdownItem, SettingsLine, SettingsSection, Stepper, Switch, TextField, ValueSlider,
+};
+use crate::utils::match_ranges;
+
+type Editor<'a, T> = Box<dyn Fn(&mut Ui, &mut T) -> Response + 'a>;
+
+enum SettingsItemKind<'a, T> {
+    Control(Editor<'a, T>),
+    Link(Option<String>),
+}
+
+struct SettingsItem<'a, T> {
+    key: String,
+    icon: String,
+    label: String,
+    keywords: Vec<String>,
+    kind: SettingsItemKind<'a, T>,
+}
+
+impl<'a, T> SettingsItem<'a, T> {
+    fn matches(&self, query: &str) -> bool {
+        query.is_empty()
+            || !match_ranges(&self.label, query).is_empty()
+            || self
+                .keywords
+                .iter()
+                .any(|keyword| !match_ranges(keyword, query).is_empty())
+    }
+}
+
+struct SettingsGroup<'a, T> {
+    title: Option<String>,
+    footer: Option<String>,
+    items: Vec<SettingsItem<'a, T>>,
+}
+
+pub struct SettingsPageResponse {
+    pub response: Response,
+    pub changed: Vec<String>,
+    pub clicked_link: Option<String>,
+}
+
+impl SettingsPageResponse {
+    pub fn changed(&self, key: &str) -> bool {
+        self.changed.iter().any(|changed| changed == key)
+    }
+}
+
+pub struct SettingsPage<'a, T> {
+    id: Id,
+    groups: Vec<SettingsGroup<'a, T>>,
+    searchable: bool,
+}
+
+impl<'a, T> SettingsPage<'a, T> {
+    pub fn new(id: impl Into<Id>) -> Self {
+        SettingsPage {
+            id: id.into(),
+            groups: Vec::new(),
+            searchable: false,
+        }
+    }
+
+    pub fn searchable(mut self, searchable: bool) -> Self {
+        self.searchable = searchable;
+        self
+    }
+
+    pub fn footer(mut self, footer: String) -> Self {
+        if let Some(group) = self.groups.last_mut() {
+            group.footer = Some(footer);
+        }
+        self
+    }
+
+    pub fn keywords(mut self, keywords: Vec<String>) -> Self {
+        if let Some(item) = self.groups.last_mut().and_then(|g| g.items.last_mut()) {
+            item.keywords = keywords;
+        }
+        self
+    }
+
+    pub fn section(mut self, title: String) -> Self {
+        self.groups.push(SettingsGroup {
+            title: Some(title),
+            footer: None,
+            items: Vec::new(),
+        });
+        self
+    }
+
+    pub fn toggle(
+        self,
+        key: String,
+        icon: String,
+        label: String,
+        value: impl Fn(&mut T) -> &mut bool + 'a,
+    ) -> Self {
+        self.control(key, icon, label, move |ui, model| {
+            ui.add(Switch::new(value(model)))
+        })
+    }
+
+    pub fn choice(
+        self,
+        key: String,
+        icon: String,
+        label: String,
+        options: Vec<impl Into<DropdownItem>>,
+        value: impl Fn(&mut T) -> &mut Option<String> + 'a,
+    ) -> Self {
+        let options: Vec<DropdownItem> = options.into_iter().map(Into::into).collect();
+        let id = key.clone();
+        self.control(key, icon, label, move |ui, model| {
+            ui.add(Dropdown::new(&id, options.clone(), value(model)))
+        })
+    }
+
+    pub fn text(
+        self,
+        key: String,
+        icon: String,
+        label: String,
+        value: impl Fn(&mut T) -> &mut String + 'a,
+    ) -> Self {
+        self.control(key, icon, label, move |ui, model| {
+            ui.add(TextField::new(value(model)))
+        })
+    }
+
+    pub fn number<N: Numeric>(
+        self,
+        key: String,
+        icon: String,
+        label: String,
+        range: RangeInclusive<N>,
+        value: impl Fn(&mut T) -> &mut N + 'a,
+    ) -> Self {
+        self.control(key, icon, label, move |ui, model| {
+            ui.add(Stepper::new(value(model), range.clone()))
+        })
+    }
+
+    pub fn slider<N: Numeric>(
+        self,
+        key: String,
+        icon: String,
+        label: String,
+        range: RangeInclusive<N>,
+        value: impl Fn(&mut T) -> &mut N + 'a,
+    ) -> Self {
+        self.control(key, icon, label, move |ui, model| {
+            ui.add(ValueSlider::new(value(model), range.clone()))
+        })
+    }
+
+    pub fn link(mut self, key: String, icon: String, label: String, value: Option<String>) -> Self {
+        self.push(SettingsItem {
+            key,
+            icon,
+            label,
+            keywords: Vec::new(),
+            kind: SettingsItemKind::Link(value),
+        });
+        self
+    }
+
+    fn control(
+        mut self,
+        key: String,
+        icon: String,
+        label: String,
+        editor: impl Fn(&mut Ui, &mut T) -> Response + 'a,
+    ) -> Self {
+        self.push(SettingsItem {
+            key,
+            icon,
+            label,
+            keywords: Vec::new(),
+            kind: SettingsItemKind::Control(Box::new(editor)),
+        });
+        self
+    }
+
+    fn push(&mut self, item: SettingsItem<'a, T>) {
+        if self.groups.is_empty() {
+            self.groups.push(SettingsGroup {
+                title: None,
+                footer: None,
+                items: Vec::new(),
+            });
+        }
+        if let Some(group) = self.groups.last_mut() {
+            group.items.push(item);
+        }
+    }
+
+    pub fn show(self, ui: &mut Ui, model: &mut T) -> SettingsPageResponse {
+        let SettingsPage {
+            id,
+            groups,
+            searchable,
+        } = self;
+        let mut changed = Vec::new();
+        let mut clicked_link = None;
+
+        let response = ui
+            .push_id(id, |ui| {
+                let mut query = String::new();
+                if searchable {
+                    let search_id = ui.id().with("search");
+                    query = ui.data(|d| d.get_temp(search_id)).unwrap_or_default();
+                    ui.add(
+                        TextEdit::singleline(&mut query)
+                            .hint_text(format!("{} Search settings…", ICON_SEARCH))
+                            .desired_width(f32::INFINITY),
+                    );
+                    ui.data_mut(|d| d.insert_temp(search_id, query.clone()));
+                    ui.add_space(ui.spacing().item_spacing.y);
+                }
+                let query = query.trim();
+
+                let groups: Vec<SettingsGroup<'a, T>> = groups
+                    .into_iter()
+                    .map(|group| SettingsGroup {
+                        title: group.title,
+                        footer: group.footer,
+                        items: group
+                            .items
+                            .into_iter()
+                            .filter(|item| item.matches(query))
+                            .collect(),
+                    })
+                    .filter(|group| !group.items.is_empty())
+                    .collect();
+
+                if groups.is_empty() && !query.is_empty() {
+                    ui.vertical_centered(|ui| {
+                        ui.add_space(16.0);
+                        ui.label(RichText::new(ICON_SEARCH_OFF).size(32.0).weak());
+                        ui.label(RichText::new(format!("No settings match \"{}\"", query)).weak());
+                    });
+                }
+
+                for group in groups {
+                    let mut section = SettingsSection::new();
+                    if let Some(title) = group.title {
+                        section = section.header(title);
+                    }
+                    if let Some(footer) = group.footer {
+                        section = section.footer(footer);
+                    }
+
+                    section.show(ui, |rows| {
+                        for item in group.items {
+                            let SettingsItem {
+                                key,
+                                icon,
+                                label,
+                                kind,
+                                ..
+                            } = item;
+                            match kind {
+                                SettingsItemKind::Control(editor) => {
+                                    let response = rows.add(
+                                        SettingsLine::new(icon, label, |ui| editor(ui, model))
+                                            .highlight(query.to_owned()),
+                                    );
+                                    if response.changed() {
+                                        changed.push(key);
+                                    }
+                                }
+                                SettingsItemKind::Link(value) => {
+                                    let response = rows.add(
+                                        SettingsLine::navigation(icon, label, value)
+                                            .highlight(query.to_owned()),
+                                    );
+                                    if response.clicked() {
+                                        clicked_link = Some(key);
+                                    }
+                                }
+                            }
+                        }
+                    });
+                    ui.add_space(ui.spacing().item_spacing.y * 2.0);
+                }
+            })
+            .response;
+
+        SettingsPageResponse {
+            response,
+            changed,
+            clicked_link,
+        }
+    }
+}
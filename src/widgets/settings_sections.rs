@@ -0,0 +1,151 @@
+use egui::{
+    pos2, Color32, InnerResponse, Layout, Rect, Response, RichText, Rounding, Shape, Stroke,
+    TextStyle, Ui, Vec2,
+};
+
+use super::SettingsLine;
+
+pub struct SettingsRows<'u> {
+    ui: &'u mut Ui,
+    inset: f32,
+    divider: Stroke,
+    last_bottom: Option<f32>,
+}
+
+impl<'u> SettingsRows<'u> {
+    pub fn add(&mut self, line: SettingsLine) -> Response {
+        if let Some(last_bottom) = self.last_bottom {
+            let y = last_bottom + self.ui.spacing().item_spacing.y / 2.0;
+            let rect = self.ui.max_rect();
+            self.ui
+                .painter()
+                .hline((rect.left() + self.inset)..=rect.right(), y, self.divider);
+        }
+        let response = self.ui.add(line);
+        self.last_bottom = Some(response.rect.bottom());
+        response
+    }
+
+    pub fn ui(&mut self) -> &mut Ui {
+        self.ui
+    }
+}
+
+pub struct SettingsSection {
+    header: Option<String>,
+    footer: Option<String>,
+}
+
+impl SettingsSection {
+    pub fn new() -> Self {
+        SettingsSection {
+            header: None,
+            footer: None,
+        }
+    }
+
+    pub fn header(mut self, header: String) -> Self {
+        self.header = Some(header);
+        self
+    }
+
+    pub fn footer(mut self, footer: String) -> Self {
+        self.footer = Some(footer);
+        self
+    }
+
+    pub fn show<R>(
+        self,
+        ui: &mut Ui,
+        add_rows: impl FnOnce(&mut SettingsRows) -> R,
+    ) -> InnerResponse<R> {
+        let padding = 8.0;
+        let dark_mode = ui.visuals().dark_mode;
+        let (fill, stroke_color, divider_color) = if dark_mode {
+            (
+                Color32::from_gray(28),
+                Color32::from_gray(56),
+                Color32::from_gray(56),
+            )
+        } else {
+            (
+                Color32::WHITE,
+                Color32::from_rgb(204, 204, 204),
+                Color32::from_rgb(224, 224, 224),
+            )
+        };
+        let hairline = 1.0 / ui.ctx().pixels_per_point();
+
+        ui.vertical(|ui| {
+            if let Some(header) = self.header {
+                ui.add_space(padding);
+                ui.horizontal(|ui| {
+                    ui.add_space(padding);
+                    ui.label(
+                        RichText::new(header.to_uppercase())
+                            .small()
+                            .extra_letter_spacing(0.6)
+                            .color(ui.visuals().weak_text_color()),
+                    );
+                });
+            }
+
+            let background = ui.painter().add(Shape::Noop);
+            let outer_rect =
+                Rect::from_min_size(ui.cursor().min, Vec2::new(ui.available_width(), 0.0));
+            let inner_rect = outer_rect.shrink(padding);
+
+            let mut content_ui = ui.child_ui(
+                Rect::from_min_max(inner_rect.min, pos2(inner_rect.max.x, f32::INFINITY)),
+                Layout::top_down(egui::Align::Min),
+                None,
+            );
+            let inset = TextStyle::Heading.resolve(content_ui.style()).size
+                + content_ui.spacing().item_spacing.x;
+            let mut rows = SettingsRows {
+                ui: &mut content_ui,
+                inset,
+                divider: Stroke::new(hairline, divider_color),
+                last_bottom: None,
+            };
+            let inner = add_rows(&mut rows);
+
+            let content_rect = content_ui.min_rect();
+            let outer_rect = Rect::from_min_max(
+                outer_rect.min,
+                pos2(outer_rect.max.x, content_rect.bottom() + padding),
+            );
+            ui.painter().set(
+                background,
+                Shape::Vec(vec![
+                    Shape::rect_filled(outer_rect, Rounding::same(8.0), fill),
+                    Shape::rect_stroke(
+                        outer_rect,
+                        Rounding::same(8.0),
+                        Stroke::new(1.0, stroke_color),
+                    ),
+                ]),
+            );
+            ui.allocate_rect(outer_rect, egui::Sense::hover());
+
+            if let Some(footer) = self.footer {
+                ui.horizontal_wrapped(|ui| {
+                    ui.add_space(padding);
+                    ui.label(
+                        RichText::new(footer)
+                            .small()
+                            .color(ui.visuals().weak_text_color()),
+                    );
+                });
+            }
+
+            inner
+        })
+    }
+}
+
+impl Default for SettingsSection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,46 +1,409 @@
-use egui::{Color32, Stroke, Widget};
+use std::sync::Arc;
+
+use egui::{
+    pos2, Color32, Galley, Pos2, Rect, Stroke, TextStyle, TextWrapMode, Visuals, Widget, WidgetText,
+};
+
+const SHAKE_DURATION: f64 = 0.4;
+const SHAKE_CYCLES: f32 = 3.0;
+const SHAKE_AMPLITUDE: f32 = 4.0;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum LabelPosition {
+    Leading,
+    Trailing,
+}
+
+#[derive(Clone)]
+pub struct SwitchStyle {
+    on_track_color: Color32,
+    off_track_color: Color32,
+    knob_color: Color32,
+    border: Option<Stroke>,
+}
+
+impl SwitchStyle {
+    pub fn new(
+        on_track_color: Color32,
+        off_track_color: Color32,
+        knob_color: Color32,
+        border: Option<Stroke>,
+    ) -> SwitchStyle {
+        SwitchStyle {
+            on_track_color,
+            off_track_color,
+            knob_color,
+            border,
+        }
+    }
+
+    pub fn from_visuals(visuals: &Visuals) -> SwitchStyle {
+        SwitchStyle {
+            on_track_color: visuals.selection.bg_fill,
+            off_track_color: visuals.widgets.inactive.bg_fill,
+            knob_color: Color32::WHITE,
+            border: Some(visuals.widgets.inactive.bg_stroke),
+        }
+    }
+}
+
+impl Default for SwitchStyle {
+    fn default() -> Self {
+        SwitchStyle {
+            on_track_color: Color32::from_rgb(91, 194, 54),
+            off_track_color: Color32::from_rgb(229, 229, 229),
+            knob_color: Color32::WHITE,
+            border: None,
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct PendingToggle {
+    previous: Option<bool>,
+    failed: bool,
+}
+
+impl PendingToggle {
+    pub fn is_pending(&self) -> bool {
+        self.previous.is_some()
+    }
+
+    pub fn confirm(&mut self) {
+        self.previous = None;
+    }
+
+    pub fn rollback(&mut self, on: &mut bool) {
+        if let Some(previous) = self.previous.take() {
+            *on = previous;
+            self.failed = true;
+        }
+    }
+}
 
 pub struct Switch<'a> {
     on: &'a mut bool,
+    pending: Option<&'a mut PendingToggle>,
+    label: Option<String>,
+    label_position: LabelPosition,
+    style: SwitchStyle,
+    disabled: bool,
 }
 
 impl<'a> Switch<'a> {
     pub fn new(on: &'a mut bool) -> Self {
-        Self { on }
+        Self {
+            on,
+            pending: None,
+            label: None,
+            label_position: LabelPosition::Trailing,
+            style: SwitchStyle::default(),
+            disabled: false,
+        }
+    }
+
+    pub fn label(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    pub fn label_position(mut self, label_position: LabelPosition) -> Self {
+        self.label_position = label_position;
+        self
+    }
+
+    pub fn style(mut self, style: SwitchStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn disable(mut self, disable: bool) -> Self {
+        self.disabled = disable;
+        self
+    }
+
+    pub fn pending(mut self, pending: &'a mut PendingToggle) -> Self {
+        self.pending = Some(pending);
+        self
     }
 }
 
 impl<'a> Widget for Switch<'a> {
-    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
-        let desired_size = ui.spacing().interact_size.y * egui::vec2(1.9, 1.1);
-        let (rect, mut response) = ui.allocate_exact_size(desired_size, egui::Sense::click());
+    fn ui(mut self, ui: &mut egui::Ui) -> egui::Response {
+        let is_pending = self.pending.as_ref().is_some_and(|p| p.is_pending());
+        let enabled = ui.is_enabled() && !self.disabled && !is_pending;
+        let sense = if enabled {
+            egui::Sense::click_and_drag()
+        } else {
+            egui::Sense::hover()
+        };
+        let (mut response, mut layout) =
+            allocate_switch(ui, self.label, self.label_position, sense);
+        let track_rect = layout.track_rect;
+
+        let knob_range = (track_rect.left() + 0.5 * track_rect.height())
+            ..=(track_rect.right() - 0.5 * track_rect.height());
+
+        let now = ui.input(|i| i.time);
+        let drag_id = response.id.with("drag");
+        let release_id = response.id.with("release");
+        let mut toggle = enabled && response.clicked();
 
-        if response.clicked() {
+        if enabled && response.dragged() {
+            if let Some(pointer) = response.interact_pointer_pos() {
+                let position = egui::remap_clamp(pointer.x, knob_range.clone(), 0.0..=1.0);
+                ui.data_mut(|d| d.insert_temp(drag_id, position));
+            }
+        }
+
+        if response.drag_stopped() {
+            if let Some(position) = ui.data_mut(|d| d.remove_temp::<f32>(drag_id)) {
+                toggle = enabled && (position > 0.5) != *self.on;
+                ui.data_mut(|d| d.insert_temp(release_id, (position, now)));
+            }
+        }
+
+        if toggle {
+            if let Some(pending) = self.pending.as_deref_mut() {
+                pending.previous = Some(*self.on);
+            }
             *self.on = !*self.on;
             response.mark_changed();
         }
 
-        if ui.is_rect_visible(rect) {
-            let how_on = ui.ctx().animate_bool_responsive(response.id, *self.on);
-            let visuals = ui.style().interact_selectable(&response, *self.on);
-            let rect = rect.expand(visuals.expansion);
+        let shake_id = response.id.with("shake");
+        if let Some(pending) = self.pending.as_deref_mut() {
+            if pending.failed {
+                pending.failed = false;
+                ui.data_mut(|d| d.insert_temp(shake_id, now));
+            }
+        }
+        let shake_offset = match ui.data(|d| d.get_temp::<f64>(shake_id)) {
+            Some(started) if now - started < SHAKE_DURATION => {
+                ui.ctx().request_repaint();
+                let t = ((now - started) / SHAKE_DURATION) as f32;
+                (t * SHAKE_CYCLES * std::f32::consts::TAU).sin() * SHAKE_AMPLITUDE * (1.0 - t)
+            }
+            Some(_) => {
+                ui.data_mut(|d| d.remove::<f64>(shake_id));
+                0.0
+            }
+            None => 0.0,
+        };
 
-            let radius = 0.5 * rect.height();
-            let knob_fill = Color32::WHITE;
-            let knob_stroke = Stroke::new(1.0, knob_fill);
-            let track_fill = if *self.on {
-                Color32::from_rgb(91, 194, 54)
+        let animated = ui.ctx().animate_bool_responsive(response.id, *self.on);
+        let how_on = if let Some(position) = ui.data(|d| d.get_temp::<f32>(drag_id)) {
+            position
+        } else if let Some((position, released)) = ui.data(|d| d.get_temp::<(f32, f64)>(release_id))
+        {
+            let t = (now - released) as f32 / ui.style().animation_time;
+            if t < 1.0 {
+                ui.ctx().request_repaint();
+                let target = if *self.on { 1.0 } else { 0.0 };
+                egui::lerp(position..=target, egui::emath::easing::cubic_out(t))
             } else {
-                Color32::from_rgb(229, 229, 229)
-            };
-            ui.painter()
-                .rect(rect, radius, track_fill, visuals.bg_stroke);
-            let circle_x = egui::lerp((rect.left() + radius)..=(rect.right() - radius), how_on);
-            let center = egui::pos2(circle_x, rect.center().y);
-            ui.painter()
-                .circle(center, 0.75 * radius, knob_fill, knob_stroke);
+                ui.data_mut(|d| d.remove::<(f32, f64)>(release_id));
+                animated
+            }
+        } else {
+            animated
+        };
+
+        if ui.is_rect_visible(response.rect) {
+            layout.track_rect = track_rect.translate(egui::vec2(shake_offset, 0.0));
+            let knob_center = paint_switch(
+                ui,
+                &response,
+                layout,
+                &self.style,
+                self.disabled,
+                how_on,
+                Some(*self.on),
+            );
+
+            if is_pending {
+                let size = 0.5 * track_rect.height();
+                egui::Spinner::new()
+                    .size(size)
+                    .color(self.style.on_track_color)
+                    .paint_at(
+                        ui,
+                        Rect::from_center_size(knob_center, egui::Vec2::splat(size)),
+                    );
+            }
+        }
+
+        response
+    }
+}
+
+/// A switch for a value that can also be mixed, such as a parent toggle over several children.
+///
+/// `None` is the mixed state and shows the knob centered. Clicking a mixed or off switch turns
+/// it on, clicking an on switch turns it off; the mixed state is only ever set by the caller.
+pub struct TriStateSwitch<'a> {
+    value: &'a mut Option<bool>,
+    label: Option<String>,
+    label_position: LabelPosition,
+    style: SwitchStyle,
+    disabled: bool,
+}
+
+impl<'a> TriStateSwitch<'a> {
+    pub fn new(value: &'a mut Option<bool>) -> Self {
+        Self {
+            value,
+            label: None,
+            label_position: LabelPosition::Trailing,
+            style: SwitchStyle::default(),
+            disabled: false,
+        }
+    }
+
+    pub fn label(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    pub fn label_position(mut self, label_position: LabelPosition) -> Self {
+        self.label_position = label_position;
+        self
+    }
+
+    pub fn style(mut self, style: SwitchStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn disable(mut self, disable: bool) -> Self {
+        self.disabled = disable;
+        self
+    }
+}
+
+impl<'a> Widget for TriStateSwitch<'a> {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        let enabled = ui.is_enabled() && !self.disabled;
+        let sense = if enabled {
+            egui::Sense::click()
+        } else {
+            egui::Sense::hover()
+        };
+        let (mut response, layout) = allocate_switch(ui, self.label, self.label_position, sense);
+
+        if enabled && response.clicked() {
+            *self.value = Some(*self.value != Some(true));
+            response.mark_changed();
+        }
+
+        let target = match *self.value {
+            Some(true) => 1.0,
+            Some(false) => 0.0,
+            None => 0.5,
+        };
+        let how_on =
+            ui.ctx()
+                .animate_value_with_time(response.id, target, ui.style().animation_time);
+
+        if ui.is_rect_visible(response.rect) {
+            paint_switch(
+                ui,
+                &response,
+                layout,
+                &self.style,
+                self.disabled,
+                how_on,
+                *self.value,
+            );
         }
 
         response
     }
 }
+
+struct SwitchLayout {
+    track_rect: Rect,
+    label: Option<(Arc<Galley>, Pos2)>,
+}
+
+fn allocate_switch(
+    ui: &mut egui::Ui,
+    label: Option<String>,
+    label_position: LabelPosition,
+    sense: egui::Sense,
+) -> (egui::Response, SwitchLayout) {
+    let track_size = ui.spacing().interact_size.y * egui::vec2(1.9, 1.1);
+    let spacing = ui.spacing().item_spacing.x;
+
+    let galley = label.map(|label| {
+        WidgetText::from(label).into_galley(
+            ui,
+            Some(TextWrapMode::Extend),
+            f32::INFINITY,
+            TextStyle::Body,
+        )
+    });
+
+    let mut desired_size = track_size;
+    if let Some(galley) = &galley {
+        desired_size.x += spacing + galley.size().x;
+        desired_size.y = desired_size.y.max(galley.size().y);
+    }
+
+    let (rect, response) = ui.allocate_exact_size(desired_size, sense);
+
+    let (track_x, label_x) = match label_position {
+        LabelPosition::Trailing => (rect.left(), rect.left() + track_size.x + spacing),
+        LabelPosition::Leading => (rect.right() - track_size.x, rect.left()),
+    };
+    let track_rect = Rect::from_min_size(
+        pos2(track_x, rect.center().y - track_size.y / 2.0),
+        track_size,
+    );
+    let label = galley.map(|galley| {
+        let label_pos = pos2(label_x, rect.center().y - galley.size().y / 2.0);
+        (galley, label_pos)
+    });
+
+    (response, SwitchLayout { track_rect, label })
+}
+
+fn paint_switch(
+    ui: &egui::Ui,
+    response: &egui::Response,
+    layout: SwitchLayout,
+    style: &SwitchStyle,
+    disabled: bool,
+    how_on: f32,
+    value: Option<bool>,
+) -> Pos2 {
+    let mut painter = ui.painter().clone();
+    if disabled {
+        painter.multiply_opacity(0.5);
+    }
+
+    if let Some((galley, label_pos)) = layout.label {
+        painter.galley(label_pos, galley, ui.visuals().text_color());
+    }
+
+    let visuals = ui
+        .style()
+        .interact_selectable(response, value != Some(false));
+    let rect = layout.track_rect.expand(visuals.expansion);
+
+    let radius = 0.5 * rect.height();
+    let knob_fill = style.knob_color;
+    let knob_stroke = Stroke::new(1.0, knob_fill);
+    let track_fill = match value {
+        Some(true) => style.on_track_color,
+        Some(false) => style.off_track_color,
+        None => style.on_track_color.linear_multiply(0.5),
+    };
+    let border = style.border.unwrap_or(visuals.bg_stroke);
+    painter.rect(rect, radius, track_fill, border);
+    let circle_x = egui::lerp((rect.left() + radius)..=(rect.right() - radius), how_on);
+    let center = egui::pos2(circle_x, rect.center().y);
+    painter.circle(center, 0.75 * radius, knob_fill, knob_stroke);
+
+    center
+}
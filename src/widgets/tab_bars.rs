@@ -1,15 +1,247 @@
-use egui::{pos2, Align2, Color32, FontFamily, FontId, Rect, Response, Sense, Stroke, Ui, Vec2};
+use egui::{
+    pos2, AboveOrBelow, Align2, Color32, EventFilter, FontFamily, FontId, Key, Modifiers, Painter,
+    PopupCloseBehavior, Rect, Response, Sense, Stroke, Ui, Vec2, Visuals,
+};
+use egui_material_icons::icons::ICON_MORE_HORIZ;
+
+const NUMBER_KEYS: [Key; 9] = [
+    Key::Num1,
+    Key::Num2,
+    Key::Num3,
+    Key::Num4,
+    Key::Num5,
+    Key::Num6,
+    Key::Num7,
+    Key::Num8,
+    Key::Num9,
+];
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum TabBarAppearance {
+    Bar,
+    Underline,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum TabBarIndicator {
+    Pill,
+    Underline,
+    None,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum TabBarOverflow {
+    Shrink,
+    Scroll,
+    More,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum TabBarPlacement {
+    Top,
+    Bottom,
+    Rail,
+    Auto,
+}
+
+#[derive(Clone, Copy)]
+pub struct TabBarStyle {
+    fill: Color32,
+    stroke: Stroke,
+    item_color: Color32,
+    selected_color: Color32,
+    icon_size: f32,
+    label_size: f32,
+    icon_spacing: f32,
+    baseline_offset: f32,
+    pill_size: Vec2,
+}
+
+impl TabBarStyle {
+    pub fn light() -> TabBarStyle {
+        TabBarStyle {
+            fill: Color32::from_rgba_premultiplied(229, 229, 234, 230),
+            stroke: Stroke::new(1.0, Color32::from_rgb(142, 142, 147)),
+            item_color: Color32::from_rgb(142, 142, 147),
+            selected_color: Color32::from_rgb(0, 122, 255),
+            icon_size: 20.0,
+            label_size: 12.0,
+            icon_spacing: 2.0,
+            baseline_offset: 6.0,
+            pill_size: Vec2::new(56.0, 28.0),
+        }
+    }
+
+    pub fn dark() -> TabBarStyle {
+        TabBarStyle {
+            fill: Color32::from_rgba_premultiplied(28, 28, 30, 230),
+            stroke: Stroke::new(1.0, Color32::from_rgb(56, 56, 58)),
+            item_color: Color32::from_rgb(142, 142, 147),
+            selected_color: Color32::from_rgb(10, 132, 255),
+            ..TabBarStyle::light()
+        }
+    }
+
+    pub fn from_visuals(visuals: &Visuals) -> TabBarStyle {
+        TabBarStyle {
+            fill: visuals.panel_fill,
+            stroke: visuals.widgets.noninteractive.bg_stroke,
+            item_color: visuals.weak_text_color(),
+            selected_color: visuals.hyperlink_color,
+            ..TabBarStyle::light()
+        }
+    }
+
+    pub fn fill(mut self, fill: Color32) -> Self {
+        self.fill = fill;
+        self
+    }
+
+    pub fn stroke(mut self, stroke: Stroke) -> Self {
+        self.stroke = stroke;
+        self
+    }
+
+    pub fn item_color(mut self, item_color: Color32) -> Self {
+        self.item_color = item_color;
+        self
+    }
+
+    pub fn selected_color(mut self, selected_color: Color32) -> Self {
+        self.selected_color = selected_color;
+        self
+    }
+
+    pub fn icon_size(mut self, icon_size: f32) -> Self {
+        self.icon_size = icon_size;
+        self
+    }
+
+    pub fn label_size(mut self, label_size: f32) -> Self {
+        self.label_size = label_size;
+        self
+    }
+
+    pub fn icon_spacing(mut self, icon_spacing: f32) -> Self {
+        self.icon_spacing = icon_spacing;
+        self
+    }
+
+    pub fn baseline_offset(mut self, baseline_offset: f32) -> Self {
+        self.baseline_offset = baseline_offset;
+        self
+    }
+
+    pub fn pill_size(mut self, pill_size: Vec2) -> Self {
+        self.pill_size = pill_size;
+        self
+    }
+
+    fn bar_baseline(&self, item_rect: Rect) -> f32 {
+        item_rect.center().y - self.baseline_offset
+    }
+
+    fn paint_bar_item(
+        &self,
+        painter: &Painter,
+        item_rect: Rect,
+        icon: &str,
+        label: &str,
+        color: Color32,
+        how_selected: f32,
+    ) {
+        let baseline = self.bar_baseline(item_rect);
+        let icon_size = self.icon_size * (1.0 + 0.1 * how_selected);
+        painter.text(
+            pos2(item_rect.center().x, baseline - self.icon_spacing / 2.0),
+            Align2::CENTER_BOTTOM,
+            icon,
+            FontId::new(icon_size, FontFamily::Proportional),
+            color,
+        );
+
+        painter.text(
+            pos2(item_rect.center().x, baseline + self.icon_spacing / 2.0),
+            Align2::CENTER_TOP,
+            label,
+            FontId::new(self.label_size, FontFamily::Proportional),
+            color,
+        );
+    }
+
+    fn paint_underline_item(
+        &self,
+        painter: &Painter,
+        item_rect: Rect,
+        icon: &str,
+        label: &str,
+        color: Color32,
+        how_selected: f32,
+    ) {
+        let scale = 1.0 + 0.05 * how_selected;
+        let icon = painter.layout_no_wrap(
+            icon.to_string(),
+            FontId::new(self.icon_size * scale, FontFamily::Proportional),
+            color,
+        );
+        let label = painter.layout_no_wrap(
+            label.to_uppercase(),
+            FontId::new(self.label_size * scale, FontFamily::Proportional),
+            color,
+        );
+        let width = icon.size().x + self.icon_spacing + label.size().x;
+        let left = item_rect.center().x - width / 2.0;
+        let label_left = left + icon.size().x + self.icon_spacing;
+        let center_y = item_rect.center().y;
+        painter.galley(pos2(left, center_y - icon.size().y / 2.0), icon, color);
+        painter.galley(
+            pos2(label_left, center_y - label.size().y / 2.0),
+            label,
+            color,
+        );
+    }
+}
+
+impl Default for TabBarStyle {
+    fn default() -> Self {
+        TabBarStyle::light()
+    }
+}
 
 pub struct TabBar<T: PartialEq + Clone> {
     tabs: Vec<TabBarItem<T>>,
     id: egui::Id,
     height: f32,
+    placement: TabBarPlacement,
+    appearance: TabBarAppearance,
+    indicator: Option<TabBarIndicator>,
+    animation_duration: Option<f32>,
+    easing: fn(f32) -> f32,
+    keyboard_shortcuts: bool,
+    overflow: TabBarOverflow,
+    min_item_width: f32,
+    rail_width: f32,
+    auto_breakpoint: f32,
+    style: Option<TabBarStyle>,
+}
+
+pub struct TabBarResponse<T> {
+    pub response: Response,
+    pub changed: bool,
+    pub previous: Option<T>,
+    /// One response per tab, in the order they were added. Tabs in the More menu only get
+    /// live responses while the menu is open.
+    pub items: Vec<Response>,
+    pub rect: Rect,
 }
 
 pub struct TabBarItem<T: PartialEq + Clone> {
     value: T,
     label: String,
     icon: String,
+    disabled: bool,
+    disabled_reason: Option<String>,
+    hidden: bool,
 }
 
 impl<T: PartialEq + Clone> TabBar<T> {
@@ -18,6 +250,17 @@ impl<T: PartialEq + Clone> TabBar<T> {
             tabs,
             id: id.into(),
             height,
+            placement: TabBarPlacement::Bottom,
+            appearance: TabBarAppearance::Bar,
+            indicator: None,
+            animation_duration: None,
+            easing: egui::emath::easing::cubic_out,
+            keyboard_shortcuts: false,
+            overflow: TabBarOverflow::Shrink,
+            min_item_width: 72.0,
+            rail_width: 80.0,
+            auto_breakpoint: 640.0,
+            style: None,
         }
     }
 
@@ -26,89 +269,694 @@ impl<T: PartialEq + Clone> TabBar<T> {
         self
     }
 
-    pub fn show(self, ctx: &egui::Context, selected: &mut T) -> Response {
-        let TabBar { tabs, id, height } = self;
-        let padding = self.height / 8.0;
+    pub fn placement(mut self, placement: TabBarPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
 
-        egui::TopBottomPanel::bottom(id)
-            .exact_height(height)
-            .show(ctx, |ui| {
-                let tab_bar_rect = ui.max_rect();
-                Self::ui_content(ui, tab_bar_rect, tabs, selected, height + padding)
-            })
+    pub fn rail_width(mut self, rail_width: f32) -> Self {
+        self.rail_width = rail_width;
+        self
+    }
+
+    pub fn auto_breakpoint(mut self, auto_breakpoint: f32) -> Self {
+        self.auto_breakpoint = auto_breakpoint;
+        self
+    }
+
+    pub fn appearance(mut self, appearance: TabBarAppearance) -> Self {
+        self.appearance = appearance;
+        self
+    }
+
+    pub fn indicator(mut self, indicator: TabBarIndicator) -> Self {
+        self.indicator = Some(indicator);
+        self
+    }
+
+    pub fn animation_duration(mut self, seconds: f32) -> Self {
+        self.animation_duration = Some(seconds);
+        self
+    }
+
+    pub fn easing(mut self, easing: fn(f32) -> f32) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    pub fn keyboard_shortcuts(mut self, keyboard_shortcuts: bool) -> Self {
+        self.keyboard_shortcuts = keyboard_shortcuts;
+        self
+    }
+
+    pub fn overflow(mut self, overflow: TabBarOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    pub fn min_item_width(mut self, min_item_width: f32) -> Self {
+        self.min_item_width = min_item_width;
+        self
+    }
+
+    pub fn style(mut self, style: TabBarStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub fn show(self, ctx: &egui::Context, selected: &mut T) -> TabBarResponse<T> {
+        let id = self.id;
+        let height = self.height;
+        let rail_width = self.rail_width;
+        let placement = self.resolve_placement(ctx.screen_rect().width());
+
+        match placement {
+            TabBarPlacement::Rail => {
+                egui::SidePanel::left(id)
+                    .exact_width(rail_width)
+                    .resizable(false)
+                    .show_separator_line(false)
+                    .frame(egui::Frame::none())
+                    .show(ctx, |ui| {
+                        let tab_bar_rect = ui.max_rect();
+                        self.ui_content(ui, tab_bar_rect, selected, true)
+                    })
+                    .inner
+            }
+            TabBarPlacement::Top => {
+                egui::TopBottomPanel::top(id)
+                    .exact_height(height)
+                    .show_separator_line(false)
+                    .frame(egui::Frame::none())
+                    .show(ctx, |ui| {
+                        let tab_bar_rect = ui.max_rect();
+                        self.ui_content(ui, tab_bar_rect, selected, false)
+                    })
+                    .inner
+            }
+            _ => {
+                egui::TopBottomPanel::bottom(id)
+                    .exact_height(height)
+                    .show_separator_line(false)
+                    .frame(egui::Frame::none())
+                    .show(ctx, |ui| {
+                        let tab_bar_rect = ui.max_rect();
+                        self.ui_content(ui, tab_bar_rect, selected, false)
+                    })
+                    .inner
+            }
+        }
+    }
+
+    pub fn show_inside(self, ui: &mut Ui, selected: &mut T) -> TabBarResponse<T> {
+        let vertical = self.resolve_placement(ui.available_width()) == TabBarPlacement::Rail;
+        let size = if vertical {
+            let shown = self.tabs.iter().filter(|item| !item.hidden).count();
+            Vec2::new(self.rail_width, self.height * shown as f32)
+        } else {
+            Vec2::new(ui.available_width(), self.height)
+        };
+        let (_, rect) = ui.allocate_space(size);
+        self.show_placed(ui, rect, selected, vertical)
+    }
+
+    pub fn show_in_rect(self, ui: &mut Ui, rect: Rect, selected: &mut T) -> TabBarResponse<T> {
+        let vertical = self.resolve_placement(rect.width()) == TabBarPlacement::Rail;
+        self.show_placed(ui, rect, selected, vertical)
+    }
+
+    pub(crate) fn split_rect(&self, rect: Rect) -> (bool, Rect, Rect) {
+        match self.resolve_placement(rect.width()) {
+            TabBarPlacement::Rail => {
+                let split = rect.left() + self.rail_width;
+                let bar = Rect::from_min_max(rect.min, pos2(split, rect.max.y));
+                let content = Rect::from_min_max(pos2(split, rect.min.y), rect.max);
+                (true, bar, content)
+            }
+            TabBarPlacement::Top => {
+                let split = rect.top() + self.height;
+                let bar = Rect::from_min_max(rect.min, pos2(rect.max.x, split));
+                let content = Rect::from_min_max(pos2(rect.min.x, split), rect.max);
+                (false, bar, content)
+            }
+            _ => {
+                let split = rect.bottom() - self.height;
+                let content = Rect::from_min_max(rect.min, pos2(rect.max.x, split));
+                let bar = Rect::from_min_max(pos2(rect.min.x, split), rect.max);
+                (false, bar, content)
+            }
+        }
+    }
+
+    pub(crate) fn values(&self) -> Vec<T> {
+        self.tabs.iter().map(|item| item.value.clone()).collect()
+    }
+
+    pub(crate) fn animation(&self) -> (Option<f32>, fn(f32) -> f32) {
+        (self.animation_duration, self.easing)
+    }
+
+    pub(crate) fn selectable_values(&self) -> Vec<T> {
+        self.tabs
+            .iter()
+            .filter(|item| !item.hidden && !item.disabled)
+            .map(|item| item.value.clone())
+            .collect()
+    }
+
+    pub(crate) fn show_placed(
+        self,
+        ui: &mut Ui,
+        rect: Rect,
+        selected: &mut T,
+        vertical: bool,
+    ) -> TabBarResponse<T> {
+        let id = self.id;
+        ui.push_id(id, |ui| self.ui_content(ui, rect, selected, vertical))
             .inner
     }
 
+    fn resolve_placement(&self, available_width: f32) -> TabBarPlacement {
+        match self.placement {
+            TabBarPlacement::Auto if available_width >= self.auto_breakpoint => {
+                TabBarPlacement::Rail
+            }
+            TabBarPlacement::Auto => TabBarPlacement::Bottom,
+            placement => placement,
+        }
+    }
+
     fn ui_content(
+        self,
         ui: &mut Ui,
         tab_bar_rect: Rect,
-        tabs: Vec<TabBarItem<T>>,
         selected: &mut T,
-        height: f32,
-    ) -> Response {
-        let response = ui.allocate_rect(tab_bar_rect, Sense::click());
+        vertical: bool,
+    ) -> TabBarResponse<T> {
+        let TabBar {
+            tabs,
+            id,
+            height,
+            appearance,
+            indicator,
+            animation_duration,
+            easing,
+            keyboard_shortcuts,
+            overflow,
+            min_item_width,
+            style,
+            ..
+        } = self;
+        let sense = if overflow == TabBarOverflow::Scroll {
+            Sense::drag()
+        } else {
+            Sense::hover()
+        };
+        let mut response = ui.allocate_rect(tab_bar_rect, sense);
+        let hidden: Vec<bool> = tabs.iter().map(|item| item.hidden).collect();
+        let (positions, tabs): (Vec<usize>, Vec<TabBarItem<T>>) = tabs
+            .into_iter()
+            .enumerate()
+            .filter(|(_, item)| !item.hidden)
+            .unzip();
+
+        let style = style.unwrap_or_else(|| {
+            if ui.visuals().dark_mode {
+                TabBarStyle::dark()
+            } else {
+                TabBarStyle::light()
+            }
+        });
+        let selected_color = style.selected_color;
 
-        let fill_color = Color32::from_rgba_premultiplied(229, 229, 234, 230);
-        let stroke_color = Color32::from_rgb(142, 142, 147);
-        let selected_color = Color32::from_rgb(0, 122, 255);
-        let stroke = Stroke::new(1.0, stroke_color);
+        match appearance {
+            TabBarAppearance::Bar => {
+                let background = tab_bar_rect.shrink(style.stroke.width / 2.0);
+                ui.painter().rect_filled(background, 2.0, style.fill);
+                ui.painter().rect_stroke(background, 2.0, style.stroke);
+            }
+            TabBarAppearance::Underline => {
+                if vertical {
+                    ui.painter()
+                        .vline(tab_bar_rect.right(), tab_bar_rect.y_range(), style.stroke);
+                } else {
+                    ui.painter()
+                        .hline(tab_bar_rect.x_range(), tab_bar_rect.bottom(), style.stroke);
+                }
+            }
+        }
 
-        if response.hovered() {
-            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+        if tabs.is_empty() {
+            return TabBarResponse {
+                changed: false,
+                previous: None,
+                items: with_hidden_placeholders(ui, id, &hidden, Vec::new()),
+                rect: tab_bar_rect,
+                response,
+            };
         }
 
-        ui.painter()
-            .rect_filled(tab_bar_rect.expand(9.0), 2.0, fill_color);
-        ui.painter()
-            .rect_stroke(tab_bar_rect.expand(9.0), 2.0, stroke);
+        let item_height = match appearance {
+            TabBarAppearance::Bar if !vertical => height + height / 8.0,
+            _ => height,
+        };
+        let main_extent = if vertical {
+            tab_bar_rect.height()
+        } else {
+            tab_bar_rect.width()
+        };
+        let min_extent = if vertical {
+            item_height
+        } else {
+            min_item_width
+        };
+        let capacity = ((main_extent / min_extent).floor() as usize).max(1);
+        let more = overflow == TabBarOverflow::More && tabs.len() > capacity;
+        let visible = if more { capacity - 1 } else { tabs.len() };
+        let slots = if more { visible + 1 } else { tabs.len() };
+        let item_extent = if vertical {
+            item_height
+        } else if overflow == TabBarOverflow::Scroll {
+            (main_extent / slots as f32).max(min_item_width)
+        } else {
+            main_extent / slots as f32
+        };
 
-        let item_width = tab_bar_rect.width() / tabs.len() as f32;
+        let scroll_id = id.with("scroll");
+        let max_scroll = (slots as f32 * item_extent - main_extent).max(0.0);
+        let mut scroll = 0.0;
+        if overflow == TabBarOverflow::Scroll {
+            scroll = ui.data(|d| d.get_temp::<f32>(scroll_id)).unwrap_or(0.0);
+            if max_scroll > 0.0 && ui.rect_contains_pointer(tab_bar_rect) {
+                let delta = ui.input_mut(|i| {
+                    let delta = i.smooth_scroll_delta;
+                    if vertical {
+                        i.smooth_scroll_delta.y = 0.0;
+                    } else {
+                        i.smooth_scroll_delta = Vec2::ZERO;
+                    }
+                    delta
+                });
+                scroll -= if vertical { delta.y } else { delta.x + delta.y };
+            }
+            let drag = response.drag_delta();
+            scroll -= if vertical { drag.y } else { drag.x };
+            scroll = scroll.clamp(0.0, max_scroll);
+            ui.set_clip_rect(tab_bar_rect.intersect(ui.clip_rect()));
+        }
 
-        for (index, item) in tabs.into_iter().enumerate() {
-            let item_rect = Rect::from_min_size(
-                pos2(
-                    tab_bar_rect.min.x + item_width * index as f32,
-                    tab_bar_rect.min.y,
-                ),
-                Vec2::new(item_width, height),
+        let item_rect_at = |position: f32, scroll: f32| {
+            let start = item_extent * position - scroll;
+            if vertical {
+                Rect::from_min_size(
+                    pos2(tab_bar_rect.min.x, tab_bar_rect.min.y + start),
+                    Vec2::new(tab_bar_rect.width(), item_height),
+                )
+            } else {
+                Rect::from_min_size(
+                    pos2(tab_bar_rect.min.x + start, tab_bar_rect.min.y),
+                    Vec2::new(item_extent, item_height),
+                )
+            }
+        };
+
+        let duration = animation_duration.unwrap_or(ui.style().animation_time);
+        let indicator = indicator.unwrap_or(match appearance {
+            TabBarAppearance::Bar => TabBarIndicator::Pill,
+            TabBarAppearance::Underline => TabBarIndicator::Underline,
+        });
+
+        let mut clicked = None;
+        let mut item_responses: Vec<Response> = tabs
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let position = positions[index];
+                if index >= visible {
+                    return ui.interact(
+                        Rect::NOTHING,
+                        id.with(("overflow", position)),
+                        Sense::hover(),
+                    );
+                }
+                let item_rect = item_rect_at(index as f32, scroll);
+                let mut item_response = if item.disabled {
+                    ui.interact(item_rect, id.with(("item", position)), Sense::hover())
+                } else {
+                    ui.interact(item_rect, id.with(("item", position)), Sense::click())
+                        .on_hover_cursor(egui::CursorIcon::PointingHand)
+                };
+                if let Some(reason) = item.disabled_reason.as_ref().filter(|_| item.disabled) {
+                    item_response = item_response.on_hover_text(reason);
+                }
+                if item_response.clicked() {
+                    clicked = Some(item.value.clone());
+                }
+                item_response
+            })
+            .collect();
+
+        let more = more.then(|| {
+            let more_rect = item_rect_at(visible as f32, scroll);
+            let more_response = ui
+                .interact(more_rect, id.with("more_item"), Sense::click())
+                .on_hover_cursor(egui::CursorIcon::PointingHand);
+            let popup_id = id.with("more");
+            if more_response.clicked() {
+                ui.memory_mut(|m| m.toggle_popup(popup_id));
+            }
+            let above_or_below = if more_rect.center().y > ui.ctx().screen_rect().center().y {
+                AboveOrBelow::Above
+            } else {
+                AboveOrBelow::Below
+            };
+            let menu_responses = egui::popup::popup_above_or_below_widget(
+                ui,
+                popup_id,
+                &more_response,
+                above_or_below,
+                PopupCloseBehavior::CloseOnClick,
+                |ui| {
+                    ui.set_min_width(160.0);
+                    let mut menu_responses = Vec::new();
+                    for item in tabs.iter().skip(visible) {
+                        let text = format!("{}  {}", item.icon, item.label);
+                        let mut item_response = ui.add_enabled(
+                            !item.disabled,
+                            egui::SelectableLabel::new(item.value == *selected, text),
+                        );
+                        if let Some(reason) = &item.disabled_reason {
+                            item_response = item_response.on_disabled_hover_text(reason);
+                        }
+                        if item_response.clicked() {
+                            clicked = Some(item.value.clone());
+                        }
+                        menu_responses.push(item_response);
+                    }
+                    menu_responses
+                },
             );
+            (more_response, menu_responses)
+        });
+        let more_response = more.map(|(more_response, menu_responses)| {
+            for (slot, menu_response) in item_responses
+                .iter_mut()
+                .skip(visible)
+                .zip(menu_responses.into_iter().flatten())
+            {
+                *slot = menu_response;
+            }
+            more_response
+        });
+
+        let focused = item_responses
+            .iter()
+            .chain(&more_response)
+            .position(|r| r.has_focus());
+        let mut target = None;
+        if let Some(focused) = focused {
+            let filter = EventFilter {
+                horizontal_arrows: !vertical,
+                vertical_arrows: vertical,
+                ..Default::default()
+            };
+            let focused_id = item_responses.iter().chain(&more_response).nth(focused);
+            if let Some(focused_id) = focused_id.map(|r| r.id) {
+                ui.memory_mut(|m| m.set_focus_lock_filter(focused_id, filter));
+            }
+            let (previous_key, next_key) = if vertical {
+                (Key::ArrowUp, Key::ArrowDown)
+            } else {
+                (Key::ArrowLeft, Key::ArrowRight)
+            };
+            let (after, before) = if more_response.as_ref().is_some_and(|r| r.has_focus()) {
+                let current = tabs
+                    .iter()
+                    .position(|item| item.value == *selected)
+                    .filter(|&index| index >= visible);
+                (
+                    current.map_or(visible, |index| index + 1),
+                    current.unwrap_or(visible),
+                )
+            } else {
+                (focused + 1, focused)
+            };
+            if ui.input_mut(|i| i.consume_key(Modifiers::NONE, next_key)) {
+                target = (after..tabs.len()).find(|&index| !tabs[index].disabled);
+            } else if ui.input_mut(|i| i.consume_key(Modifiers::NONE, previous_key)) {
+                target = (0..before).rev().find(|&index| !tabs[index].disabled);
+            }
+        }
 
-            let item_response = ui.allocate_rect(item_rect, Sense::click());
+        if keyboard_shortcuts {
+            let current = tabs
+                .iter()
+                .position(|item| item.value == *selected)
+                .unwrap_or(0);
+            let count = tabs.len();
+            let enabled = |index: &usize| !tabs[*index].disabled;
+            ui.input_mut(|i| {
+                if i.consume_key(Modifiers::CTRL | Modifiers::SHIFT, Key::Tab) {
+                    target = (1..count)
+                        .map(|step| (current + count - step) % count)
+                        .find(enabled);
+                } else if i.consume_key(Modifiers::CTRL, Key::Tab) {
+                    target = (1..count)
+                        .map(|step| (current + step) % count)
+                        .find(enabled);
+                }
+                for (index, key) in NUMBER_KEYS.into_iter().enumerate() {
+                    if index < count && enabled(&index) && i.consume_key(Modifiers::COMMAND, key) {
+                        target = Some(index);
+                    }
+                }
+            });
+        }
+
+        if let Some(target) = target {
+            if focused.is_some() {
+                match &more_response {
+                    Some(more_response) if target >= visible => more_response.request_focus(),
+                    _ => item_responses[target].request_focus(),
+                }
+            }
+            clicked = Some(tabs[target].value.clone());
+        }
 
-            if item_response.clicked() {
-                *selected = item.value.clone();
+        if clicked.is_none()
+            && !tabs
+                .iter()
+                .any(|item| item.value == *selected && !item.disabled)
+        {
+            clicked = tabs
+                .iter()
+                .find(|item| !item.disabled)
+                .map(|item| item.value.clone());
+        }
+
+        let mut previous = None;
+        if let Some(value) = clicked {
+            if value != *selected {
+                response.mark_changed();
+                previous = Some(std::mem::replace(selected, value));
                 ui.ctx().request_repaint();
             }
+        }
 
-            let label_color = if *selected == item.value {
+        let selected_index = tabs.iter().position(|item| item.value == *selected);
+        let selected_slot = selected_index.map(|index| index.min(visible));
+
+        if overflow == TabBarOverflow::Scroll {
+            if let (Some(slot), Some(_)) = (selected_slot, &previous) {
+                let start = slot as f32 * item_extent;
+                if start < scroll {
+                    scroll = start;
+                } else if start + item_extent > scroll + main_extent {
+                    scroll = start + item_extent - main_extent;
+                }
+            }
+            ui.data_mut(|d| d.insert_temp(scroll_id, scroll));
+        }
+
+        if let Some(selected_slot) = selected_slot {
+            let position = animate_position(
+                ui,
+                id.with("indicator"),
+                selected_slot as f32,
+                duration,
+                easing,
+            );
+            let item_rect = item_rect_at(position, scroll);
+            let icon_center = match appearance {
+                TabBarAppearance::Bar => pos2(
+                    item_rect.center().x,
+                    style.bar_baseline(item_rect) - (style.icon_spacing + style.icon_size) / 2.0,
+                ),
+                TabBarAppearance::Underline => item_rect.center(),
+            };
+
+            match indicator {
+                TabBarIndicator::Pill => {
+                    let pill_width = (item_rect.width() * 0.6).min(style.pill_size.x);
+                    let pill_size = Vec2::new(pill_width, style.pill_size.y);
+                    let pill = Rect::from_center_size(icon_center, pill_size);
+                    ui.painter().rect_filled(
+                        pill,
+                        style.pill_size.y / 2.0,
+                        selected_color.gamma_multiply(0.15),
+                    );
+                }
+                TabBarIndicator::Underline => {
+                    let line = if vertical {
+                        Rect::from_min_max(
+                            pos2(item_rect.right() - 2.0, item_rect.top()),
+                            item_rect.right_bottom(),
+                        )
+                    } else {
+                        Rect::from_min_max(
+                            pos2(item_rect.left(), item_rect.bottom() - 2.0),
+                            item_rect.right_bottom(),
+                        )
+                    };
+                    ui.painter().rect_filled(line, 0.0, selected_color);
+                }
+                TabBarIndicator::None => {}
+            }
+        }
+
+        let slot_responses = item_responses.iter().take(visible).chain(&more_response);
+        for (slot, slot_response) in slot_responses.enumerate() {
+            let (icon, label, disabled) = match tabs.get(slot) {
+                Some(item) if slot < visible => {
+                    (item.icon.as_str(), item.label.as_str(), item.disabled)
+                }
+                _ => (ICON_MORE_HORIZ, "More", false),
+            };
+            let item_rect = item_rect_at(slot as f32, scroll);
+            let is_selected = selected_slot == Some(slot);
+            let how_selected = easing(ui.ctx().animate_bool_with_time(
+                slot_response.id.with("selected"),
+                is_selected,
+                duration,
+            ));
+            let label_color = if is_selected {
                 selected_color
+            } else if disabled {
+                style.item_color.gamma_multiply(0.4)
             } else {
-                stroke_color
+                style.item_color
             };
 
-            ui.painter().text(
-                item_rect.center() - Vec2::new(0.0, 6.0),
-                Align2::CENTER_BOTTOM,
-                &item.icon,
-                FontId::new(20.0, FontFamily::Proportional),
-                label_color,
-            );
+            if slot_response.has_focus() {
+                ui.painter().rect_stroke(
+                    item_rect.shrink(3.0),
+                    6.0,
+                    Stroke::new(2.0, selected_color),
+                );
+            }
 
-            ui.painter().text(
-                item_rect.center() - Vec2::new(0.0, 6.0),
-                Align2::CENTER_TOP,
-                item.label,
-                FontId::new(12.0, FontFamily::Proportional),
-                label_color,
-            );
+            let painter = ui.painter();
+            match appearance {
+                TabBarAppearance::Bar => {
+                    style.paint_bar_item(painter, item_rect, icon, label, label_color, how_selected)
+                }
+                TabBarAppearance::Underline => style.paint_underline_item(
+                    painter,
+                    item_rect,
+                    icon,
+                    label,
+                    label_color,
+                    how_selected,
+                ),
+            }
         }
 
-        response
+        TabBarResponse {
+            changed: previous.is_some(),
+            previous,
+            items: with_hidden_placeholders(ui, id, &hidden, item_responses),
+            rect: tab_bar_rect,
+            response,
+        }
     }
 }
 
+fn with_hidden_placeholders(
+    ui: &Ui,
+    id: egui::Id,
+    hidden: &[bool],
+    shown: Vec<Response>,
+) -> Vec<Response> {
+    let mut shown = shown.into_iter();
+    hidden
+        .iter()
+        .enumerate()
+        .map(|(index, &hidden)| {
+            let response = if hidden { None } else { shown.next() };
+            response.unwrap_or_else(|| {
+                ui.interact(Rect::NOTHING, id.with(("hidden", index)), Sense::hover())
+            })
+        })
+        .collect()
+}
+
+fn animate_position(
+    ui: &Ui,
+    id: egui::Id,
+    target: f32,
+    duration: f32,
+    easing: fn(f32) -> f32,
+) -> f32 {
+    let now = ui.input(|i| i.time);
+    let (from, to, started) = ui
+        .data(|d| d.get_temp::<(f32, f32, f64)>(id))
+        .unwrap_or((target, target, now));
+
+    let t = if duration > 0.0 {
+        ((now - started) as f32 / duration).clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+    let current = egui::lerp(from..=to, easing(t));
+
+    if to != target {
+        ui.data_mut(|d| d.insert_temp(id, (current, target, now)));
+        ui.ctx().request_repaint();
+        return current;
+    }
+
+    ui.data_mut(|d| d.insert_temp(id, (from, to, started)));
+    if t < 1.0 {
+        ui.ctx().request_repaint();
+    }
+    current
+}
+
 impl<T: Clone + PartialEq> TabBarItem<T> {
     pub fn new(value: T, label: String, icon: String) -> Self {
-        TabBarItem { label, icon, value }
+        TabBarItem {
+            label,
+            icon,
+            value,
+            disabled: false,
+            disabled_reason: None,
+            hidden: false,
+        }
+    }
+
+    pub fn disable(mut self, disable: bool) -> Self {
+        self.disabled = disable;
+        self
+    }
+
+    pub fn disabled_reason(mut self, reason: String) -> Self {
+        self.disabled = true;
+        self.disabled_reason = Some(reason);
+        self
+    }
+
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
     }
 }
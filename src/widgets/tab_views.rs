@@ -0,0 +1,250 @@
+use std::collections::HashSet;
+
+use egui::{vec2, Align, Id, Layout, Pos2, Rect, ScrollArea, Sense, Ui};
+
+use super::{TabBar, TabBarResponse};
+
+const SWIPE_THRESHOLD: f32 = 0.25;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum TabTransition {
+    None,
+    Slide,
+    Fade,
+}
+
+type Page<'a> = Box<dyn FnMut(&mut Ui) + 'a>;
+type PageInit<'a> = Box<dyn FnOnce() + 'a>;
+
+struct TabPages<'a, T> {
+    id: Id,
+    pages: Vec<(T, Page<'a>)>,
+    inits: Vec<(T, PageInit<'a>)>,
+    transition: TabTransition,
+    swipe: bool,
+    order: Vec<T>,
+    selectable: Vec<T>,
+    duration: Option<f32>,
+    easing: fn(f32) -> f32,
+}
+
+pub struct TabView<'a, T: PartialEq + Clone> {
+    tab_bar: TabBar<T>,
+    pages: TabPages<'a, T>,
+}
+
+impl<'a, T: PartialEq + Clone> TabView<'a, T> {
+    pub fn new(id: impl Into<Id>, tab_bar: TabBar<T>) -> Self {
+        TabView {
+            tab_bar,
+            pages: TabPages {
+                id: id.into(),
+                pages: Vec::new(),
+                inits: Vec::new(),
+                transition: TabTransition::Slide,
+                swipe: true,
+                order: Vec::new(),
+                selectable: Vec::new(),
+                duration: None,
+                easing: egui::emath::easing::cubic_out,
+            },
+        }
+    }
+
+    pub fn page(mut self, value: T, content: impl FnMut(&mut Ui) + 'a) -> Self {
+        self.pages.pages.push((value, Box::new(content)));
+        self
+    }
+
+    pub fn on_first_visit(mut self, value: T, init: impl FnOnce() + 'a) -> Self {
+        self.pages.inits.push((value, Box::new(init)));
+        self
+    }
+
+    pub fn transition(mut self, transition: TabTransition) -> Self {
+        self.pages.transition = transition;
+        self
+    }
+
+    pub fn swipe(mut self, swipe: bool) -> Self {
+        self.pages.swipe = swipe;
+        self
+    }
+
+    pub fn show(self, ctx: &egui::Context, selected: &mut T) -> TabBarResponse<T> {
+        let TabView { tab_bar, mut pages } = self;
+
+        pages.bind(&tab_bar);
+        let mut response = tab_bar.show(ctx, selected);
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let rect = ui.available_rect_before_wrap();
+            ui.allocate_rect(rect, Sense::hover());
+            pages.show(ui, rect, selected, &mut response);
+        });
+        response
+    }
+
+    pub fn show_inside(self, ui: &mut Ui, selected: &mut T) -> TabBarResponse<T> {
+        let TabView { tab_bar, mut pages } = self;
+
+        let rect = ui.available_rect_before_wrap();
+        ui.allocate_rect(rect, Sense::hover());
+        let (vertical, bar_rect, page_rect) = tab_bar.split_rect(rect);
+        pages.bind(&tab_bar);
+
+        let mut response = tab_bar.show_placed(ui, bar_rect, selected, vertical);
+        pages.show(ui, page_rect, selected, &mut response);
+        response
+    }
+}
+
+impl<'a, T: PartialEq + Clone> TabPages<'a, T> {
+    fn bind(&mut self, tab_bar: &TabBar<T>) {
+        self.order = tab_bar.values();
+        self.selectable = tab_bar.selectable_values();
+        (self.duration, self.easing) = tab_bar.animation();
+    }
+
+    fn show(mut self, ui: &mut Ui, rect: Rect, selected: &mut T, response: &mut TabBarResponse<T>) {
+        if self.swipe {
+            if let Some(target) = self.swipe_target(ui, rect, selected) {
+                let previous = std::mem::replace(selected, target);
+                response.response.mark_changed();
+                response.changed = true;
+                response.previous = Some(previous);
+            }
+        }
+
+        let Some(current) = self.pages.iter().position(|(value, _)| value == selected) else {
+            return;
+        };
+
+        let visited_id = self.id.with("visited");
+        let mut visited: HashSet<usize> = ui.data(|d| d.get_temp(visited_id)).unwrap_or_default();
+        if visited.insert(current) {
+            for (value, init) in self.inits.drain(..) {
+                if value == *selected {
+                    init();
+                }
+            }
+            ui.data_mut(|d| d.insert_temp(visited_id, visited));
+        }
+
+        let now = ui.input(|i| i.time);
+        let transition_id = self.id.with("transition");
+        let (from, to, started) = ui
+            .data(|d| d.get_temp::<(usize, usize, f64)>(transition_id))
+            .unwrap_or((current, current, now));
+        let (from, started) = if to != current {
+            (to, now)
+        } else {
+            (from, started)
+        };
+        ui.data_mut(|d| d.insert_temp(transition_id, (from, current, started)));
+
+        let duration = self.duration.unwrap_or(ui.style().animation_time);
+        let t = if self.transition == TabTransition::None
+            || from == current
+            || from >= self.pages.len()
+            || duration <= 0.0
+        {
+            1.0
+        } else {
+            ((now - started) as f32 / duration).clamp(0.0, 1.0)
+        };
+
+        if t >= 1.0 {
+            self.show_page(ui, rect, current, 0.0, 1.0, true);
+            return;
+        }
+
+        ui.ctx().request_repaint();
+        let t = (self.easing)(t);
+        let direction = if self.tab_position(current) > self.tab_position(from) {
+            1.0
+        } else {
+            -1.0
+        };
+        match self.transition {
+            TabTransition::Slide => {
+                self.show_page(ui, rect, from, -direction * t * rect.width(), 1.0, false);
+                let offset = direction * (1.0 - t) * rect.width();
+                self.show_page(ui, rect, current, offset, 1.0, true);
+            }
+            _ => {
+                self.show_page(ui, rect, from, 0.0, 1.0 - t, false);
+                self.show_page(ui, rect, current, 0.0, t, true);
+            }
+        }
+    }
+
+    fn tab_position(&self, index: usize) -> Option<usize> {
+        let value = &self.pages[index].0;
+        self.order.iter().position(|tab| tab == value)
+    }
+
+    fn show_page(
+        &mut self,
+        ui: &mut Ui,
+        rect: Rect,
+        index: usize,
+        offset: f32,
+        opacity: f32,
+        interactive: bool,
+    ) {
+        let mut page_ui = ui.child_ui_with_id_source(
+            rect.translate(vec2(offset, 0.0)),
+            Layout::top_down(Align::Min),
+            ("page", index),
+            None,
+        );
+        page_ui.set_clip_rect(rect.intersect(ui.clip_rect()));
+        if opacity < 1.0 {
+            page_ui.multiply_opacity(opacity);
+        }
+        if !interactive {
+            page_ui.disable();
+        }
+
+        let page = &mut self.pages[index].1;
+        ScrollArea::vertical()
+            .id_source(self.id.with(("page", index)))
+            .auto_shrink([false, false])
+            .show(&mut page_ui, |ui| page(ui));
+    }
+
+    fn swipe_target(&self, ui: &Ui, rect: Rect, selected: &T) -> Option<T> {
+        let swipe_id = self.id.with("swipe");
+        let (pressed, released, touching, position) = ui.input(|i| {
+            (
+                i.pointer.primary_pressed(),
+                i.pointer.primary_released(),
+                i.any_touches(),
+                i.pointer.latest_pos(),
+            )
+        });
+
+        if pressed && touching {
+            if let Some(origin) = position.filter(|pos| rect.contains(*pos)) {
+                ui.data_mut(|d| d.insert_temp(swipe_id, origin));
+            }
+        }
+        if !released {
+            return None;
+        }
+
+        let origin: Pos2 = ui.data_mut(|d| d.remove_temp(swipe_id))?;
+        let delta = position? - origin;
+        if delta.x.abs() < SWIPE_THRESHOLD * rect.width() || delta.x.abs() < delta.y.abs() {
+            return None;
+        }
+
+        let current = self.selectable.iter().position(|value| value == selected)?;
+        let target = if delta.x < 0.0 {
+            current + 1
+        } else {
+            current.checked_sub(1)?
+        };
+        self.selectable.get(target).cloned()
+    }
+}